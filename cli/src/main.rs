@@ -6,9 +6,10 @@ use solana_sdk::{bs58, instruction::Instruction, pubkey::Pubkey, signature::{rea
 use std::time::Duration;
 use std::{convert::TryFrom, fmt::Display, fs::File, str::FromStr, sync::Arc};
 use solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType};
-use anchor_lang::Discriminator;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator};
 use solana_account_decoder::UiAccountEncoding;
 use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use serde::Serialize;
 use thiserror::Error;
 
 fn pause_subcommand() -> Command<'static> {
@@ -27,6 +28,173 @@ fn open_subcommand() -> Command<'static> {
     Command::new("open").about("open pool for new stakes")
 }
 
+fn window_subcommand() -> Command<'static> {
+    Command::new("window")
+        .about("set staking window, pool is open for new stakes without limit if not set")
+        .arg(
+            Arg::new("opens_at")
+                .long("opens-at")
+                .takes_value(true)
+                .required(false)
+                .help("Unix timestamp the pool opens for new stakes"),
+        )
+        .arg(
+            Arg::new("closes_at")
+                .long("closes-at")
+                .takes_value(true)
+                .required(false)
+                .help("Unix timestamp the pool closes for new stakes"),
+        )
+}
+
+fn merkle_subcommand() -> Command<'static> {
+    Command::new("merkle")
+        .about("build allowlist merkle tree from csv and print root with proofs of all wallets")
+        .arg(
+            Arg::new("csv")
+                .index(1)
+                .takes_value(true)
+                .required(true)
+                .help("CSV file with wallet and optional positions allowance per tier on each line"),
+        )
+        .arg(
+            Arg::new("set")
+                .long("set")
+                .takes_value(false)
+                .required(false)
+                .help("Set the tree root as pool allowlist, it gates new user creation only and doesn't revoke existing users"),
+        )
+}
+
+fn solvency_subcommand() -> Command<'static> {
+    Command::new("solvency")
+        .about("enable or disable solvency mode, new stakes require reward vault covering all rewards")
+        .arg(
+            Arg::new("enabled")
+                .index(1)
+                .takes_value(true)
+                .required(true)
+                .help("Solvency mode state, true or false"),
+        )
+}
+
+fn fund_subcommand() -> Command<'static> {
+    Command::new("fund")
+        .about("fund reward vault from token account of authority")
+        .arg(
+            Arg::new("address")
+                .index(1)
+                .takes_value(true)
+                .required(true)
+                .help("The source token account of reward tokens"),
+        )
+        .arg(
+            Arg::new("amount")
+                .index(2)
+                .takes_value(true)
+                .required(true)
+                .help("The funded amount"),
+        )
+}
+
+fn funding_cap_subcommand() -> Command<'static> {
+    Command::new("funding-cap")
+        .about("enable or disable funding cap, funding above the total payable reward is refused")
+        .arg(
+            Arg::new("enabled")
+                .index(1)
+                .takes_value(true)
+                .required(true)
+                .help("Funding cap state, true or false"),
+        )
+}
+
+fn set_authority_subcommand() -> Command<'static> {
+    Command::new("set-authority")
+        .about("propose new pool authority, the proposed authority accepts it with --accept")
+        .arg(
+            Arg::new("new_authority")
+                .index(1)
+                .takes_value(true)
+                .required(false)
+                .help("The proposed authority, the pending proposal is cancelled if not set"),
+        )
+        .arg(
+            Arg::new("accept")
+                .long("accept")
+                .takes_value(false)
+                .required(false)
+                .conflicts_with("new_authority")
+                .help("Accept authority proposed to the --authority keypair"),
+        )
+}
+
+fn role_subcommand() -> Command<'static> {
+    Command::new("role")
+        .about("assign admin role to key, the authority acts in the role if key is not set")
+        .arg(
+            Arg::new("role")
+                .index(1)
+                .takes_value(true)
+                .required(true)
+                .help("The role: pauser, operator or treasurer"),
+        )
+        .arg(
+            Arg::new("key")
+                .index(2)
+                .takes_value(true)
+                .required(false)
+                .help("The key assigned to the role"),
+        )
+}
+
+fn emergency_subcommand() -> Command<'static> {
+    Command::new("emergency").about("enable emergency withdraw of principal, even if paused")
+}
+
+fn projection_subcommand() -> Command<'static> {
+    Command::new("projection")
+        .about("project reward vesting of tier for stake amount")
+        .arg(
+            Arg::new("tier")
+                .index(1)
+                .takes_value(true)
+                .required(true)
+                .help("The reward tier index"),
+        )
+        .arg(
+            Arg::new("amount")
+                .index(2)
+                .takes_value(true)
+                .required(true)
+                .help("The stake amount"),
+        )
+        .arg(
+            Arg::new("steps")
+                .index(3)
+                .takes_value(true)
+                .required(false)
+                .default_value("10")
+                .help("The number of projection points over duration"),
+        )
+}
+
+fn summary_subcommand() -> Command<'static> {
+    Command::new("summary").about("show pool metrics, vault balances and available slots")
+}
+
+fn pending_subcommand() -> Command<'static> {
+    Command::new("pending")
+        .about("show pending rewards of user positions")
+        .arg(
+            Arg::new("wallet")
+                .index(1)
+                .takes_value(true)
+                .required(true)
+                .help("The user wallet address"),
+        )
+}
+
 fn withdraw_subcommand() -> Command<'static> {
     Command::new("withdraw")
         .about("withdraw extra from vaults")
@@ -37,6 +205,91 @@ fn withdraw_subcommand() -> Command<'static> {
                 .required(true)
                 .help("The destination token account for CSM tokens"),
         )
+        .arg(
+            Arg::new("reward_address")
+                .index(2)
+                .takes_value(true)
+                .required(false)
+                .help("The destination token account for reward tokens, if reward mint differs"),
+        )
+        .arg(
+            Arg::new("vault")
+                .long("vault")
+                .takes_value(true)
+                .required(false)
+                .requires("amount")
+                .help("Withdraw only from the vault: stake or reward, extra reward can be withdrawn from open pool"),
+        )
+        .arg(
+            Arg::new("amount")
+                .long("amount")
+                .takes_value(true)
+                .required(false)
+                .requires("vault")
+                .help("The withdrawn amount from the vault"),
+        )
+}
+
+fn withdraw_delay_subcommand() -> Command<'static> {
+    Command::new("withdraw-delay")
+        .about("set delay of queued withdrawals, withdraw of all extra is allowed only without delay")
+        .arg(
+            Arg::new("delay")
+                .index(1)
+                .takes_value(true)
+                .required(true)
+                .help("The delay in seconds, can only be increased and never removed"),
+        )
+}
+
+fn queue_withdraw_subcommand() -> Command<'static> {
+    Command::new("queue-withdraw")
+        .about("queue withdrawal of extra from vault, executable after the pool withdraw delay")
+        .arg(
+            Arg::new("vault")
+                .index(1)
+                .takes_value(true)
+                .required(true)
+                .help("The vault: stake or reward"),
+        )
+        .arg(
+            Arg::new("amount")
+                .index(2)
+                .takes_value(true)
+                .required(true)
+                .help("The withdrawn amount"),
+        )
+        .arg(
+            Arg::new("address")
+                .index(3)
+                .takes_value(true)
+                .required(true)
+                .help("The destination token account"),
+        )
+}
+
+fn execute_withdraw_subcommand() -> Command<'static> {
+    Command::new("execute-withdraw")
+        .about("execute queued withdrawal")
+        .arg(
+            Arg::new("index")
+                .index(1)
+                .takes_value(true)
+                .required(true)
+                .help("The withdrawal index"),
+        )
+}
+
+fn cancel_withdraw_subcommand() -> Command<'static> {
+    Command::new("cancel-withdraw")
+        .about("cancel queued withdrawal")
+        .arg(
+            Arg::new("index")
+                .index(1)
+                .takes_value(true)
+                .required(true)
+                .help("The withdrawal index"),
+        )
 }
 
 fn free_subcommand() -> Command<'static> {
@@ -57,7 +310,22 @@ enum CliCommand {
     Unpause,
     Close,
     Open,
-    Withdraw { address: Pubkey },
+    Window { opens_at: Option<u64>, closes_at: Option<u64> },
+    Merkle { csv: String, set: bool },
+    Solvency { enabled: bool },
+    Fund { address: Pubkey, amount: u64 },
+    FundingCap { enabled: bool },
+    SetAuthority { new_authority: Option<Pubkey>, accept: bool },
+    Role { role: staking::state::Role, key: Option<Pubkey> },
+    Emergency,
+    Projection { tier: u8, amount: u64, steps: u64 },
+    Summary,
+    Pending { wallet: Pubkey },
+    Withdraw { address: Pubkey, reward_address: Pubkey, amount: Option<(staking::state::VaultKind, u64)> },
+    WithdrawDelay { delay: u64 },
+    QueueWithdraw { vault: staking::state::VaultKind, amount: u64, address: Pubkey },
+    ExecuteWithdraw { index: u64 },
+    CancelWithdraw { index: u64 },
     Free { address: Pubkey },
 }
 
@@ -68,7 +336,22 @@ impl Display for CliCommand {
             CliCommand::Unpause => write!(f, "unpause"),
             CliCommand::Close => write!(f, "close"),
             CliCommand::Open => write!(f, "open"),
-            CliCommand::Withdraw { address } => write!(f, "withdraw {}", address),
+            CliCommand::Window { opens_at, closes_at } => write!(f, "window {:?} {:?}", opens_at, closes_at),
+            CliCommand::Merkle { csv, set } => write!(f, "merkle {} {}", csv, set),
+            CliCommand::Solvency { enabled } => write!(f, "solvency {}", enabled),
+            CliCommand::Fund { address, amount } => write!(f, "fund {} {}", address, amount),
+            CliCommand::FundingCap { enabled } => write!(f, "funding-cap {}", enabled),
+            CliCommand::SetAuthority { new_authority, accept } => write!(f, "set-authority {:?} {}", new_authority, accept),
+            CliCommand::Role { role, key } => write!(f, "role {:?} {:?}", role, key),
+            CliCommand::Emergency => write!(f, "emergency"),
+            CliCommand::Projection { tier, amount, steps } => write!(f, "projection {} {} {}", tier, amount, steps),
+            CliCommand::Summary => write!(f, "summary"),
+            CliCommand::Pending { wallet } => write!(f, "pending {}", wallet),
+            CliCommand::Withdraw { address, reward_address, amount } => write!(f, "withdraw {} {} {:?}", address, reward_address, amount),
+            CliCommand::WithdrawDelay { delay } => write!(f, "withdraw-delay {}", delay),
+            CliCommand::QueueWithdraw { vault, amount, address } => write!(f, "queue-withdraw {:?} {} {}", vault, amount, address),
+            CliCommand::ExecuteWithdraw { index } => write!(f, "execute-withdraw {}", index),
+            CliCommand::CancelWithdraw { index } => write!(f, "cancel-withdraw {}", index),
             CliCommand::Free { address } => write!(f, "free {}", address),
         }
     }
@@ -83,8 +366,92 @@ impl TryFrom<&ArgMatches> for CliCommand {
             Some(("unpause", _matches)) => Ok(CliCommand::Unpause),
             Some(("close", _matches)) => Ok(CliCommand::Close),
             Some(("open", _matches)) => Ok(CliCommand::Open),
-            Some(("withdraw", matches)) => Ok(CliCommand::Withdraw {
+            Some(("window", matches)) => Ok(CliCommand::Window {
+                opens_at: parse_optional_number("opens_at", matches)?,
+                closes_at: parse_optional_number("closes_at", matches)?,
+            }),
+            Some(("merkle", matches)) => Ok(CliCommand::Merkle {
+                csv: parse_string("csv", matches)?,
+                set: matches.is_present("set"),
+            }),
+            Some(("solvency", matches)) => Ok(CliCommand::Solvency {
+                enabled: parse_number("enabled", matches)?,
+            }),
+            Some(("fund", matches)) => Ok(CliCommand::Fund {
                 address: parse_pubkey("address", matches)?,
+                amount: parse_number("amount", matches)?,
+            }),
+            Some(("funding-cap", matches)) => Ok(CliCommand::FundingCap {
+                enabled: parse_number("enabled", matches)?,
+            }),
+            Some(("set-authority", matches)) => {
+                let new_authority = if matches.is_present("new_authority") {
+                    Some(parse_pubkey("new_authority", matches)?)
+                } else {
+                    None
+                };
+                Ok(CliCommand::SetAuthority {
+                    new_authority,
+                    accept: matches.is_present("accept"),
+                })
+            }
+            Some(("role", matches)) => {
+                let role = match parse_string("role", matches)?.as_str() {
+                    "pauser" => staking::state::Role::Pauser,
+                    "operator" => staking::state::Role::Operator,
+                    "treasurer" => staking::state::Role::Treasurer,
+                    _ => return Err(CliError::BadParameter("role".into())),
+                };
+                let key = if matches.is_present("key") {
+                    Some(parse_pubkey("key", matches)?)
+                } else {
+                    None
+                };
+                Ok(CliCommand::Role { role, key })
+            }
+            Some(("emergency", _matches)) => Ok(CliCommand::Emergency),
+            Some(("projection", matches)) => {
+                let steps = parse_number("steps", matches)?;
+                if steps == 0 {
+                    return Err(CliError::BadParameter("steps".into()));
+                }
+                Ok(CliCommand::Projection {
+                    tier: parse_number("tier", matches)?,
+                    amount: parse_number("amount", matches)?,
+                    steps,
+                })
+            }
+            Some(("summary", _matches)) => Ok(CliCommand::Summary),
+            Some(("pending", matches)) => Ok(CliCommand::Pending {
+                wallet: parse_pubkey("wallet", matches)?,
+            }),
+            Some(("withdraw", matches)) => {
+                let address = parse_pubkey("address", matches)?;
+                let reward_address = if matches.is_present("reward_address") {
+                    parse_pubkey("reward_address", matches)?
+                } else {
+                    address
+                };
+                let amount = if matches.is_present("vault") {
+                    Some((parse_vault("vault", matches)?, parse_number("amount", matches)?))
+                } else {
+                    None
+                };
+                Ok(CliCommand::Withdraw { address, reward_address, amount })
+            }
+            Some(("withdraw-delay", matches)) => Ok(CliCommand::WithdrawDelay {
+                delay: parse_number("delay", matches)?,
+            }),
+            Some(("queue-withdraw", matches)) => Ok(CliCommand::QueueWithdraw {
+                vault: parse_vault("vault", matches)?,
+                amount: parse_number("amount", matches)?,
+                address: parse_pubkey("address", matches)?,
+            }),
+            Some(("execute-withdraw", matches)) => Ok(CliCommand::ExecuteWithdraw {
+                index: parse_number("index", matches)?,
+            }),
+            Some(("cancel-withdraw", matches)) => Ok(CliCommand::CancelWithdraw {
+                index: parse_number("index", matches)?,
             }),
             Some(("free", matches)) => Ok(CliCommand::Free {
                 address: parse_pubkey("address", matches)?,
@@ -101,6 +468,28 @@ fn parse_pubkey(arg: &str, matches: &ArgMatches) -> Result<Pubkey, CliError> {
         .map_err(|_err| CliError::BadParameter(arg.into()))
 }
 
+fn parse_number<T: FromStr>(arg: &str, matches: &ArgMatches) -> Result<T, CliError> {
+    parse_string(arg, matches)?
+        .parse::<T>()
+        .map_err(|_err| CliError::BadParameter(arg.into()))
+}
+
+fn parse_optional_number<T: FromStr>(arg: &str, matches: &ArgMatches) -> Result<Option<T>, CliError> {
+    if matches.is_present(arg) {
+        parse_number(arg, matches).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+fn parse_vault(arg: &str, matches: &ArgMatches) -> Result<staking::state::VaultKind, CliError> {
+    match parse_string(arg, matches)?.as_str() {
+        "stake" => Ok(staking::state::VaultKind::Stake),
+        "reward" => Ok(staking::state::VaultKind::Reward),
+        _ => Err(CliError::BadParameter(arg.into())),
+    }
+}
+
 fn parse_string(arg: &str, matches: &ArgMatches) -> Result<String, CliError> {
     Ok(matches
         .value_of(arg)
@@ -114,6 +503,10 @@ enum CliError {
     BadParameter(String),
     #[error("Command not recognized: {0}")]
     CommandNotRecognized(String),
+    #[error("Simulation failed: {0}")]
+    SimulationFailed(String),
+    #[error("RPC request failed: {0}")]
+    RpcFailed(String),
 }
 
 struct CliConfig {
@@ -177,7 +570,22 @@ fn main() -> Result<(), CliError> {
         .subcommand(unpause_subcommand())
         .subcommand(close_subcommand())
         .subcommand(open_subcommand())
+        .subcommand(window_subcommand())
+        .subcommand(merkle_subcommand())
+        .subcommand(solvency_subcommand())
+        .subcommand(fund_subcommand())
+        .subcommand(funding_cap_subcommand())
+        .subcommand(set_authority_subcommand())
+        .subcommand(role_subcommand())
+        .subcommand(emergency_subcommand())
+        .subcommand(projection_subcommand())
+        .subcommand(summary_subcommand())
+        .subcommand(pending_subcommand())
         .subcommand(withdraw_subcommand())
+        .subcommand(withdraw_delay_subcommand())
+        .subcommand(queue_withdraw_subcommand())
+        .subcommand(execute_withdraw_subcommand())
+        .subcommand(cancel_withdraw_subcommand())
         .subcommand(free_subcommand())
         .get_matches();
 
@@ -205,7 +613,22 @@ fn main() -> Result<(), CliError> {
         CliCommand::Unpause => unpause(&client, pool, &authority),
         CliCommand::Close => close(&client, pool, &authority),
         CliCommand::Open => open(&client, pool, &authority),
-        CliCommand::Withdraw { address } => withdraw(&client, pool, &authority, address),
+        CliCommand::Window { opens_at, closes_at } => window(&client, pool, &authority, opens_at, closes_at),
+        CliCommand::Merkle { csv, set } => merkle(&client, pool, &authority, csv, set),
+        CliCommand::Solvency { enabled } => solvency(&client, pool, &authority, enabled),
+        CliCommand::Fund { address, amount } => fund(&client, pool, &authority, address, amount),
+        CliCommand::FundingCap { enabled } => funding_cap(&client, pool, &authority, enabled),
+        CliCommand::SetAuthority { new_authority, accept } => set_authority(&client, pool, &authority, new_authority, accept),
+        CliCommand::Role { role, key } => set_role(&client, pool, &authority, role, key),
+        CliCommand::Emergency => emergency(&client, pool, &authority),
+        CliCommand::Projection { tier, amount, steps } => projection(&client, pool, tier, amount, steps),
+        CliCommand::Summary => summary(&client, pool),
+        CliCommand::Pending { wallet } => pending(&client, pool, wallet),
+        CliCommand::Withdraw { address, reward_address, amount } => withdraw(&client, pool, &authority, address, reward_address, amount),
+        CliCommand::WithdrawDelay { delay } => withdraw_delay(&client, pool, &authority, delay),
+        CliCommand::QueueWithdraw { vault, amount, address } => queue_withdraw(&client, pool, &authority, vault, amount, address),
+        CliCommand::ExecuteWithdraw { index } => execute_withdraw(&client, pool, &authority, index),
+        CliCommand::CancelWithdraw { index } => cancel_withdraw(&client, pool, &authority, index),
         CliCommand::Free { address } => free(&client, pool, &authority, address),
     }
 }
@@ -234,8 +657,214 @@ fn open(client: &Arc<Client>, pool: Pubkey, authority: &Keypair) -> Result<(), C
     Ok(())
 }
 
-fn withdraw(client: &Arc<Client>, pool: Pubkey, authority: &Keypair, destionation: Pubkey) -> Result<(), CliError> {
-    let ix = staking::instructions::withdraw(pool, authority.pubkey(), destionation);
+fn window(client: &Arc<Client>, pool: Pubkey, authority: &Keypair, opens_at: Option<u64>, closes_at: Option<u64>) -> Result<(), CliError> {
+    let ix = staking::instructions::set_staking_window(pool, authority.pubkey(), opens_at, closes_at);
+    sign_and_submit(client, &[ix], authority);
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct AllowlistProof {
+    wallet: String,
+    allowance: u16,
+    proof: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Allowlist {
+    root: String,
+    proofs: Vec<AllowlistProof>,
+}
+
+fn merkle(client: &Arc<Client>, pool: Pubkey, authority: &Keypair, csv: String, set: bool) -> Result<(), CliError> {
+    let wallets = read_allowlist(&csv)?;
+    let leaves = wallets.iter().map(|(wallet, allowance)| staking::merkle::leaf(wallet, *allowance)).collect();
+    let levels = merkle_levels(leaves);
+    let root = levels[levels.len() - 1][0];
+
+    let allowlist = Allowlist {
+        root: bs58::encode(root).into_string(),
+        proofs: wallets.iter().enumerate().map(|(index, (wallet, allowance))| AllowlistProof {
+            wallet: wallet.to_string(),
+            allowance: *allowance,
+            proof: merkle_proof(&levels, index).iter().map(|node| bs58::encode(node).into_string()).collect(),
+        }).collect(),
+    };
+    println!("{}", serde_json::to_string_pretty(&allowlist).unwrap());
+
+    if set {
+        let ix = staking::instructions::set_merkle_root(pool, authority.pubkey(), Some(root));
+        sign_and_submit(client, &[ix], authority);
+    }
+    Ok(())
+}
+
+/// Read `wallet[,allowance]` lines, empty lines and lines starting with # are skipped
+fn read_allowlist(csv: &str) -> Result<Vec<(Pubkey, u16)>, CliError> {
+    let content = std::fs::read_to_string(csv)
+        .map_err(|_| CliError::BadParameter("csv".into()))?;
+
+    let mut wallets = vec![];
+    for line in content.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+        let mut columns = line.split(',').map(str::trim);
+        let wallet = Pubkey::from_str(columns.next().unwrap_or_default())
+            .map_err(|_| CliError::BadParameter(format!("csv wallet {}", line)))?;
+        let allowance = match columns.next() {
+            Some(allowance) if !allowance.is_empty() => allowance.parse::<u16>()
+                .map_err(|_| CliError::BadParameter(format!("csv allowance {}", line)))?,
+            _ => 0,
+        };
+        wallets.push((wallet, allowance));
+    }
+
+    if wallets.is_empty() {
+        return Err(CliError::BadParameter("csv".into()));
+    }
+    Ok(wallets)
+}
+
+/// Levels of the tree from leaves to root, odd node is moved to the next level as is
+fn merkle_levels(leaves: Vec<[u8; 32]>) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![leaves];
+    while levels[levels.len() - 1].len() > 1 {
+        let level = levels[levels.len() - 1].chunks(2).map(|pair| match pair {
+            [a, b] => staking::merkle::parent(a, b),
+            [a] => *a,
+            _ => unreachable!(),
+        }).collect();
+        levels.push(level);
+    }
+    levels
+}
+
+fn merkle_proof(levels: &[Vec<[u8; 32]>], mut index: usize) -> Vec<[u8; 32]> {
+    let mut proof = vec![];
+    for level in &levels[..levels.len() - 1] {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        index /= 2;
+    }
+    proof
+}
+
+fn solvency(client: &Arc<Client>, pool: Pubkey, authority: &Keypair, enabled: bool) -> Result<(), CliError> {
+    let ix = staking::instructions::set_solvency_check(pool, authority.pubkey(), enabled);
+    sign_and_submit(client, &[ix], authority);
+    Ok(())
+}
+
+fn fund(client: &Arc<Client>, pool: Pubkey, authority: &Keypair, source: Pubkey, amount: u64) -> Result<(), CliError> {
+    let pool_account = get_pool(client, &pool)?;
+    let ix = staking::instructions::fund_rewards(pool, &pool_account, authority.pubkey(), source, amount);
+    sign_and_submit(client, &[ix], authority);
+    Ok(())
+}
+
+fn funding_cap(client: &Arc<Client>, pool: Pubkey, authority: &Keypair, enabled: bool) -> Result<(), CliError> {
+    let ix = staking::instructions::set_funding_cap(pool, authority.pubkey(), enabled);
+    sign_and_submit(client, &[ix], authority);
+    Ok(())
+}
+
+fn set_authority(client: &Arc<Client>, pool: Pubkey, authority: &Keypair, new_authority: Option<Pubkey>, accept: bool) -> Result<(), CliError> {
+    let ix = if accept {
+        staking::instructions::accept_authority(pool, authority.pubkey())
+    } else {
+        staking::instructions::propose_authority(pool, authority.pubkey(), new_authority)
+    };
+    sign_and_submit(client, &[ix], authority);
+    Ok(())
+}
+
+fn set_role(client: &Arc<Client>, pool: Pubkey, authority: &Keypair, role: staking::state::Role, key: Option<Pubkey>) -> Result<(), CliError> {
+    let ix = staking::instructions::set_role(pool, authority.pubkey(), role, key);
+    sign_and_submit(client, &[ix], authority);
+    Ok(())
+}
+
+fn emergency(client: &Arc<Client>, pool: Pubkey, authority: &Keypair) -> Result<(), CliError> {
+    let ix = staking::instructions::enable_emergency(pool, authority.pubkey());
+    sign_and_submit(client, &[ix], authority);
+    Ok(())
+}
+
+fn projection(client: &Arc<Client>, pool: Pubkey, tier: u8, amount: u64, steps: u64) -> Result<(), CliError> {
+    if steps == 0 {
+        return Err(CliError::BadParameter("steps".into()));
+    }
+    let pool_account = get_pool(client, &pool)?;
+    let reward_tier = pool_account.tiers.get(tier as usize)
+        .ok_or_else(|| CliError::BadParameter("tier".into()))?;
+    let reward = reward_tier.reward(amount)
+        .map_err(|_| CliError::BadParameter("amount".into()))?;
+
+    println!("Reward: {}", reward);
+    for step in 0..=steps {
+        // Multiplied in u128, the result is at most the duration
+        let elapsed = (reward_tier.duration as u128 * step as u128 / steps as u128) as u64;
+        let vested = reward_tier.curve.vested(reward, elapsed, reward_tier.duration);
+        println!("{:>12} {:>20}", elapsed, vested);
+    }
+    Ok(())
+}
+
+fn summary(client: &Arc<Client>, pool: Pubkey) -> Result<(), CliError> {
+    let pool_account = get_pool(client, &pool)?;
+    let ix = staking::instructions::pool_summary(pool, &pool_account);
+    let stats: staking::instructions::PoolStats = simulate_view(client, &[ix])?;
+    println!("{:#?}", stats);
+    Ok(())
+}
+
+fn pending(client: &Arc<Client>, pool: Pubkey, wallet: Pubkey) -> Result<(), CliError> {
+    let (user, _bump) = Pubkey::find_program_address(&[pool.as_ref(), wallet.as_ref()], &staking::ID);
+    let positions = get_position_accounts(client, &user)?;
+    let ix = staking::instructions::pending_rewards(pool, user, &positions);
+    let rewards: staking::instructions::UserRewards = simulate_view(client, &[ix])?;
+    for position in rewards.positions.iter() {
+        println!("{} tier {} {:>20}{}", position.position, position.tier, position.amount, if position.unlocked { " unlocked" } else { "" });
+    }
+    println!("Total: {}", rewards.total);
+    Ok(())
+}
+
+fn withdraw(client: &Arc<Client>, pool: Pubkey, authority: &Keypair, destionation: Pubkey, reward_destination: Pubkey, amount: Option<(staking::state::VaultKind, u64)>) -> Result<(), CliError> {
+    let pool_account = get_pool(client, &pool)?;
+    let ix = match amount {
+        Some((vault, amount)) => staking::instructions::withdraw_amount(pool, &pool_account, authority.pubkey(), destionation, reward_destination, vault, amount),
+        None => staking::instructions::withdraw(pool, &pool_account, authority.pubkey(), destionation, reward_destination),
+    };
+    sign_and_submit(client, &[ix], authority);
+    Ok(())
+}
+
+fn withdraw_delay(client: &Arc<Client>, pool: Pubkey, authority: &Keypair, delay: u64) -> Result<(), CliError> {
+    let ix = staking::instructions::set_withdraw_delay(pool, authority.pubkey(), delay);
+    sign_and_submit(client, &[ix], authority);
+    Ok(())
+}
+
+fn queue_withdraw(client: &Arc<Client>, pool: Pubkey, authority: &Keypair, vault: staking::state::VaultKind, amount: u64, destination: Pubkey) -> Result<(), CliError> {
+    let pool_account = get_pool(client, &pool)?;
+    println!("Withdrawal index: {}", pool_account.next_withdrawal);
+    let ix = staking::instructions::queue_withdraw(pool, &pool_account, authority.pubkey(), destination, vault, amount);
+    sign_and_submit(client, &[ix], authority);
+    Ok(())
+}
+
+fn execute_withdraw(client: &Arc<Client>, pool: Pubkey, authority: &Keypair, index: u64) -> Result<(), CliError> {
+    let pool_account = get_pool(client, &pool)?;
+    let withdrawal = staking::instructions::withdrawal_address(pool, index);
+    let withdrawal_account = get_withdrawal(client, &withdrawal)?;
+    let ix = staking::instructions::execute_withdraw(pool, &pool_account, authority.pubkey(), withdrawal, withdrawal_account.to);
+    sign_and_submit(client, &[ix], authority);
+    Ok(())
+}
+
+fn cancel_withdraw(client: &Arc<Client>, pool: Pubkey, authority: &Keypair, index: u64) -> Result<(), CliError> {
+    let withdrawal = staking::instructions::withdrawal_address(pool, index);
+    let ix = staking::instructions::cancel_withdraw(pool, authority.pubkey(), withdrawal);
     sign_and_submit(client, &[ix], authority);
     Ok(())
 }
@@ -254,11 +883,54 @@ fn free_user(client: &Arc<Client>, pool: Pubkey, authority: &Keypair, user: Pubk
 }
 
 fn free_pool(client: &Arc<Client>, pool: Pubkey, authority: &Keypair, receiver: Pubkey) -> Result<(), CliError> {
-    let ix = staking::instructions::free_pool(pool, authority.pubkey(),  receiver);
+    let pool_account = get_pool(client, &pool)?;
+    let ix = staking::instructions::free_pool(pool, &pool_account, authority.pubkey(), receiver);
     sign_and_submit(client, &[ix], authority);
     Ok(())
 }
 
+fn get_pool(client: &Arc<Client>, pool: &Pubkey) -> Result<staking::state::Pool, CliError> {
+    let data = client.get_account_data(pool)
+        .map_err(|_| CliError::BadParameter("pool".into()))?;
+    staking::state::Pool::try_deserialize(&mut data.as_slice())
+        .map_err(|_| CliError::BadParameter("pool".into()))
+}
+
+fn get_withdrawal(client: &Arc<Client>, withdrawal: &Pubkey) -> Result<staking::state::Withdrawal, CliError> {
+    let data = client.get_account_data(withdrawal)
+        .map_err(|_| CliError::BadParameter("index".into()))?;
+    staking::state::Withdrawal::try_deserialize(&mut data.as_slice())
+        .map_err(|_| CliError::BadParameter("index".into()))
+}
+
+fn get_position_accounts(client: &Arc<Client>, user: &Pubkey) -> Result<Vec<Pubkey>, CliError> {
+    let account_type_filter = RpcFilterType::Memcmp(Memcmp {
+        offset: 0,
+        bytes: MemcmpEncodedBytes::Base58(bs58::encode(staking::state::Position::discriminator()).into_string()),
+        encoding: None,
+    });
+
+    let user_filter = RpcFilterType::Memcmp(Memcmp {
+        offset: 8 + 32,
+        bytes: MemcmpEncodedBytes::Base58(bs58::encode(user.to_bytes()).into_string()),
+        encoding: None,
+    });
+
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![account_type_filter, user_filter]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            data_slice: None,
+            commitment: None,
+        },
+        with_context: None,
+    };
+
+    client.get_program_accounts_with_config(&staking::ID, config)
+        .map(|accounts| accounts.into_iter().map(|(key, _)| key).collect())
+        .map_err(|_| CliError::BadParameter("wallet".into()))
+}
+
 fn get_user_accounts(client: &Arc<Client>, pool: &Pubkey) -> Result<Vec<Pubkey>, CliError> {
     let account_type_filter = RpcFilterType::Memcmp(Memcmp {
         offset: 0,
@@ -290,6 +962,29 @@ fn get_user_accounts(client: &Arc<Client>, pool: &Pubkey) -> Result<Vec<Pubkey>,
     )
 }
 
+/// Simulate view instruction and decode its return data from the program logs
+fn simulate_view<T: AnchorDeserialize>(client: &Arc<Client>, ixs: &[Instruction]) -> Result<T, CliError> {
+    let mut tx = Transaction::new_with_payer(ixs, Some(&client.payer_pubkey()));
+    let blockhash = client
+        .latest_blockhash()
+        .map_err(|err| CliError::RpcFailed(err.to_string()))?;
+    tx.sign(&vec![&client.payer], blockhash);
+    let result = client
+        .simulate_transaction(&tx)
+        .map_err(|err| CliError::RpcFailed(err.to_string()))?
+        .value;
+    if let Some(err) = result.err {
+        return Err(CliError::SimulationFailed(err.to_string()));
+    }
+
+    let prefix = format!("Program return: {} ", staking::ID);
+    let data = result.logs.unwrap_or_default().iter()
+        .find_map(|log| log.strip_prefix(prefix.as_str()).map(str::to_string))
+        .and_then(|data| base64::decode(data).ok())
+        .ok_or_else(|| CliError::SimulationFailed("no return data".into()))?;
+    T::try_from_slice(&data).map_err(|err| CliError::SimulationFailed(err.to_string()))
+}
+
 fn sign_and_submit(client: &Arc<Client>, ixs: &[Instruction], authority: &Keypair) {
     let mut tx = Transaction::new_with_payer(ixs, Some(&client.payer_pubkey()));
     tx.sign(
@@ -32,4 +32,76 @@ pub enum StakingError {
     UserHasActiveStakes,
     AmountMustBeZero,
     PoolHasToBeClosed,
+
+    #[msg("Invalid tier.")]
+    InvalidTier,
+
+    #[msg("Tier already has stakers.")]
+    TierHasStakers,
+
+    #[msg("The mint is not allowed.")]
+    MintNotAllowed,
+    #[msg("Too many mints in allowlist.")]
+    TooManyAllowedMints,
+
+    #[msg("Invalid mint.")]
+    InvalidMint,
+
+    #[msg("The time lock has passed.")]
+    TimeLockHasPassed,
+    #[msg("Penalty requires a treasury if reward mint differs from mint.")]
+    TreasuryRequired,
+
+    #[msg("Pool is in emergency mode.")]
+    EmergencyMode,
+    #[msg("Emergency withdraw is not enabled.")]
+    EmergencyNotEnabled,
+
+    #[msg("Max positions per wallet reached for this tier.")]
+    TierCapReached,
+    #[msg("Invalid position.")]
+    InvalidPosition,
+
+    #[msg("Stake amount is out of tier range.")]
+    InvalidStakeAmount,
+
+    #[msg("Signer is not the receipt holder or user delegate.")]
+    InvalidDelegate,
+    #[msg("Reward must be sent to the user reward destination.")]
+    InvalidRewardDestination,
+
+    #[msg("Position can't be transferred to the same wallet.")]
+    InvalidRecipient,
+
+    #[msg("Signer doesn't hold the position receipt.")]
+    NotReceiptHolder,
+
+    #[msg("Pool is not open for staking yet.")]
+    PoolNotOpenYet,
+    #[msg("Staking window must open before it closes.")]
+    InvalidStakingWindow,
+
+    #[msg("Allowlist proof is required.")]
+    ProofRequired,
+    #[msg("Wallet is not in the allowlist.")]
+    InvalidProof,
+
+    #[msg("Reward vault can't cover rewards of the new stake.")]
+    InsufficientRewardFunds,
+
+    #[msg("Funding exceeds the total reward the pool can pay out.")]
+    FundingCapExceeded,
+
+    #[msg("Signer is not the pending authority.")]
+    NotPendingAuthority,
+
+    #[msg("Signer doesn't have the role for this action.")]
+    InvalidRole,
+
+    #[msg("Withdrawals have to be queued while withdraw delay is set.")]
+    WithdrawDelayed,
+    #[msg("Withdrawal can't be executed before its delay has passed.")]
+    WithdrawalLocked,
+    #[msg("Withdraw delay can only be increased, up to the max delay.")]
+    InvalidWithdrawDelay,
 }
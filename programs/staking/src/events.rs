@@ -1,21 +1,207 @@
-use crate::Tier;
+use crate::{RewardTier, Role, TierUpdate, VaultKind};
 use anchor_lang::prelude::*;
 
+#[event]
+pub struct InitializeEvent {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub reward_mint: Pubkey,
+    pub tiers: Vec<RewardTier>,
+    pub opens_at: Option<u64>,
+    pub closes_at: Option<u64>,
+}
+
+#[event]
+pub struct PauseEvent {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct UnpauseEvent {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct OpenEvent {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct CloseEvent {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct ProposeAuthorityEvent {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+}
+
+#[event]
+pub struct AcceptAuthorityEvent {
+    pub pool: Pubkey,
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct SetRoleEvent {
+    pub pool: Pubkey,
+    pub role: Role,
+    pub key: Option<Pubkey>,
+}
+
+#[event]
+pub struct WithdrawExtraEvent {
+    pub pool: Pubkey,
+    pub to: Pubkey,
+    pub reward_to: Pubkey,
+    pub amount: u64,
+    pub reward_amount: u64,
+}
+
+#[event]
+pub struct WithdrawEvent {
+    pub pool: Pubkey,
+    pub vault: VaultKind,
+    pub amount: u64,
+    pub to: Pubkey,
+}
+
+#[event]
+pub struct SetWithdrawDelayEvent {
+    pub pool: Pubkey,
+    pub withdraw_delay: u64,
+}
+
+#[event]
+pub struct QueueWithdrawEvent {
+    pub pool: Pubkey,
+    pub withdrawal: Pubkey,
+    pub vault: VaultKind,
+    pub amount: u64,
+    pub to: Pubkey,
+    pub executable_at: u64,
+}
+
+#[event]
+pub struct ExecuteWithdrawEvent {
+    pub pool: Pubkey,
+    pub withdrawal: Pubkey,
+    pub vault: VaultKind,
+    pub amount: u64,
+    pub to: Pubkey,
+}
+
+#[event]
+pub struct CancelWithdrawEvent {
+    pub pool: Pubkey,
+    pub withdrawal: Pubkey,
+}
+
+#[event]
+pub struct CreateUserEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub authority: Pubkey,
+    pub allowance: u16,
+}
+
+#[event]
+pub struct FreeUserEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub authority: Pubkey,
+    pub receiver: Pubkey,
+}
+
+#[event]
+pub struct SetDelegateEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub delegate: Option<Pubkey>,
+    pub reward_destination: Option<Pubkey>,
+}
+
+#[event]
+pub struct FreePoolEvent {
+    pub pool: Pubkey,
+    pub receiver: Pubkey,
+}
+
 #[event]
 pub struct StakeEvent {
     pub pool: Pubkey,
     pub user: Pubkey,
-    pub tier: Tier,
+    pub position: Pubkey,
+    pub receipt: Pubkey,
+    pub tier: u8,
     pub locked_until: u64,
     pub amount: u64,
+    pub fee: u64,
+    pub reward: u64,
 }
 
 #[event]
 pub struct UnstakeEvent {
     pub pool: Pubkey,
     pub user: Pubkey,
-    pub tier: Tier,
+    pub position: Pubkey,
+    pub tier: u8,
     pub amount: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct EarlyUnstakeEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub position: Pubkey,
+    pub tier: u8,
+    pub amount: u64,
+    pub fee: u64,
+    pub penalty: u64,
+    pub reward: u64,
+    pub forfeited: u64,
+}
+
+#[event]
+pub struct EmergencyWithdrawEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub position: Pubkey,
+    pub tier: u8,
+    pub amount: u64,
+    pub fee: u64,
+    pub forfeited: u64,
+}
+
+#[event]
+pub struct ExitEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub position: Pubkey,
+    pub tier: u8,
+    pub amount: u64,
+    pub fee: u64,
+    pub reward: u64,
+    pub reward_fee: u64,
+}
+
+#[event]
+pub struct TransferPositionEvent {
+    pub pool: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub from_position: Pubkey,
+    pub to_position: Pubkey,
+    pub tier: u8,
 }
 
 #[event]
@@ -23,4 +209,66 @@ pub struct ClaimEvent {
     pub pool: Pubkey,
     pub user: Pubkey,
     pub amount: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct ClaimTierEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub tier: u8,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct FundEvent {
+    pub pool: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct UpdateTierEvent {
+    pub pool: Pubkey,
+    pub tier: u8,
+    pub update: TierUpdate,
+}
+
+#[event]
+pub struct SetTreasuryEvent {
+    pub pool: Pubkey,
+    pub treasury: Pubkey,
+}
+
+#[event]
+pub struct EnableEmergencyEvent {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct SetStakingWindowEvent {
+    pub pool: Pubkey,
+    pub opens_at: Option<u64>,
+    pub closes_at: Option<u64>,
+}
+
+#[event]
+pub struct SetMerkleRootEvent {
+    pub pool: Pubkey,
+    pub merkle_root: Option<[u8; 32]>,
+}
+
+#[event]
+pub struct SetSolvencyCheckEvent {
+    pub pool: Pubkey,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct SetFundingCapEvent {
+    pub pool: Pubkey,
+    pub enabled: bool,
 }
@@ -0,0 +1,28 @@
+use crate::errors::StakingError;
+use crate::state::*;
+use crate::ID;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        constraint = pool.pending_authority == Some(pending_authority.key()) @ StakingError::NotPendingAuthority,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub pending_authority: Signer<'info>,
+}
+
+pub fn accept_authority(pool: Pubkey, pending_authority: Pubkey) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(pool, false),
+            AccountMeta::new_readonly(pending_authority, true),
+        ],
+        data: crate::instruction::AcceptAuthority.data(),
+    }
+}
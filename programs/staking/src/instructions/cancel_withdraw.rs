@@ -0,0 +1,42 @@
+use crate::errors::StakingError;
+use crate::state::*;
+use crate::ID;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+
+#[derive(Accounts)]
+pub struct CancelWithdraw<'info> {
+    #[account(
+        constraint = pool.role_key(Role::Treasurer) == authority.key() @ StakingError::InvalidRole,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        close = authority,
+        has_one = pool,
+        seeds = [
+            b"withdrawal".as_ref(),
+            pool.key().as_ref(),
+            withdrawal.index.to_le_bytes().as_ref(),
+        ],
+        bump = withdrawal.bump,
+    )]
+    pub withdrawal: Account<'info, Withdrawal>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn cancel_withdraw(pool: Pubkey, authority: Pubkey, withdrawal: Pubkey) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new_readonly(pool, false),
+            AccountMeta::new(withdrawal, false),
+            AccountMeta::new(authority, true),
+        ],
+        data: crate::instruction::CancelWithdraw.data(),
+    }
+}
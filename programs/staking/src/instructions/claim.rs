@@ -1,7 +1,7 @@
 use crate::state::*;
+use crate::token_interface;
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer};
 
 use crate::errors::StakingError;
 
@@ -10,6 +10,8 @@ pub struct Claim<'info> {
     #[account(
         mut,
         has_one = reward_vault,
+        has_one = reward_mint,
+        has_one = reward_token_program,
         constraint = !pool.paused @ StakingError::PoolPaused,
     )]
     pub pool: Account<'info, Pool>,
@@ -24,40 +26,112 @@ pub struct Claim<'info> {
     pub pool_signer: UncheckedAccount<'info>,
 
     #[account(
-        mut,
-        has_one = authority,
         has_one = pool,
         seeds = [
             pool.key().as_ref(),
-            authority.key().as_ref(),
+            user.authority.as_ref(),
         ],
         bump = user.bump,
     )]
     pub user: Account<'info, User>,
 
+    /// Receipt holder or user delegate
     pub authority: Signer<'info>,
 
-    #[account(mut)]
-    pub to: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = token_interface::mint(&to)? == pool.reward_mint @ StakingError::InvalidMint,
+    )]
+    /// CHECK: token account of reward_mint
+    pub to: UncheckedAccount<'info>,
 
     #[account(
         mut,
         seeds = [b"reward".as_ref(), pool.key().as_ref()],
         bump = pool.reward_vault_bump,
     )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    /// CHECK: the reward vault PDA
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// CHECK: pool reward mint
+    pub reward_mint: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: pool reward token program
+    pub reward_token_program: UncheckedAccount<'info>,
 }
 
-impl<'a, 'b, 'c, 'info> From<&mut Claim<'info>> for CpiContext<'a, 'b, 'c, 'info, Transfer<'info>> {
-    fn from(accounts: &mut Claim<'info>) -> CpiContext<'a, 'b, 'c, 'info, Transfer<'info>> {
-        let cpi_accounts = Transfer {
-            from: accounts.reward_vault.to_account_info(),
-            to: accounts.to.to_account_info(),
-            authority: accounts.pool_signer.to_account_info(),
-        };
-        let cpi_program = accounts.token_program.to_account_info();
-        CpiContext::new(cpi_program, cpi_accounts)
+impl<'info> Claim<'info> {
+    /// Pay out vested reward of the staking positions with receipt holders,
+    /// returns claimed amount and fee
+    pub fn claim_positions(
+        &mut self,
+        positions: &mut [(Account<'info, Position>, Pubkey)],
+    ) -> Result<(u64, u64)> {
+        let signer = self.authority.key();
+        let to = self.to.key();
+        for (_, holder) in positions.iter() {
+            require!(
+                self.user.can_claim(&signer, holder),
+                StakingError::InvalidDelegate
+            );
+            require!(
+                self.user.allows_claim_to(&to, &signer, holder),
+                StakingError::InvalidRewardDestination
+            );
+        }
+
+        require!(
+            positions
+                .iter()
+                .any(|(position, _)| matches!(position.status, StakeStatus::Staking { .. })),
+            StakingError::UserDoensntHaveStakes
+        );
+
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp as u64;
+
+        let new_state: Vec<(u64, StakeStatus)> = positions
+            .iter()
+            .map(|(position, _)| {
+                let tier = &self.pool.tiers[position.tier as usize];
+                position.status.update_reword(tier, now)
+            })
+            .collect();
+
+        let amount: u64 = new_state.iter().map(|(value, _)| value).sum();
+
+        require!(amount > 0, StakingError::AmountMustBeGreaterThanZero);
+
+        let fee = self.transfer_reward(amount)?;
+
+        for ((position, _), new_status) in positions
+            .iter_mut()
+            .zip(new_state.into_iter().map(|(_, status)| status))
+        {
+            position.status = new_status;
+            position.exit(&crate::ID)?;
+        }
+
+        self.pool.metrics.claim(amount);
+
+        Ok((amount, fee))
+    }
+
+    /// Transfer reward from reward vault, returns the fee withheld from amount
+    pub fn transfer_reward(&self, amount: u64) -> Result<u64> {
+        let key = self.pool.key();
+        let seeds = [key.as_ref(), &[self.pool.bump]];
+
+        token_interface::transfer_checked(
+            &self.reward_token_program,
+            &self.reward_vault,
+            &self.reward_mint,
+            &self.to,
+            &self.pool_signer,
+            amount,
+            &[&seeds],
+        )?;
+
+        token_interface::transfer_fee(&self.reward_mint, amount)
     }
 }
@@ -10,7 +10,7 @@ use crate::errors::StakingError;
 pub struct ClosePool<'info> {
     #[account(
         mut,
-        has_one = authority,
+        constraint = pool.role_key(Role::Operator) == authority.key() @ StakingError::InvalidRole,
         constraint = !pool.paused @ StakingError::PoolPaused,
         constraint = !pool.closed,
     )]
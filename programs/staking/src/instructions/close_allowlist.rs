@@ -0,0 +1,20 @@
+use crate::state::*;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CloseAllowlist<'info> {
+    #[account(
+        mut,
+        close = receiver,
+        has_one = authority,
+        seeds = [b"allowlist".as_ref()],
+        bump = allowlist.bump,
+    )]
+    pub allowlist: Account<'info, Allowlist>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub receiver: SystemAccount<'info>,
+}
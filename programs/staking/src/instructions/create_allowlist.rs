@@ -0,0 +1,28 @@
+use crate::program::Staking;
+use crate::state::*;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateAllowlist<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Allowlist::space(),
+        seeds = [b"allowlist".as_ref()],
+        bump,
+    )]
+    pub allowlist: Account<'info, Allowlist>,
+
+    /// Upgrade authority of the program
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Staking>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()))]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
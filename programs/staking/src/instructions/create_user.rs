@@ -14,7 +14,7 @@ pub struct CreateUser<'info> {
     #[account(
         init,
         payer = authority,
-        space = User::space(),
+        space = User::space(pool.tiers.len()),
         seeds = [
             pool.key().as_ref(),
             authority.key().as_ref(),
@@ -0,0 +1,182 @@
+use crate::state::*;
+use crate::token_interface;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::StakingError;
+
+#[derive(Accounts)]
+pub struct EarlyUnstake<'info> {
+    #[account(
+        mut,
+        has_one = vault,
+        has_one = mint,
+        has_one = token_program,
+        has_one = reward_vault,
+        has_one = reward_mint,
+        has_one = reward_token_program,
+        constraint = !pool.paused @ StakingError::PoolPaused,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        seeds = [
+            pool.key().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    /// CHECK: only for key()
+    pub pool_signer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = pool,
+        seeds = [
+            pool.key().as_ref(),
+            user.authority.as_ref(),
+        ],
+        bump = user.bump,
+    )]
+    pub user: Box<Account<'info, User>>,
+
+    #[account(
+        mut,
+        close = authority,
+        has_one = user,
+        seeds = [
+            user.key().as_ref(),
+            position.index.to_le_bytes().as_ref(),
+        ],
+        bump = position.bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"receipt".as_ref(), position.key().as_ref()],
+        bump = position.receipt_bump,
+    )]
+    /// CHECK: the position receipt mint PDA
+    pub receipt_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = holds_receipt(&receipt, &receipt_mint.key(), &authority.key()) @ StakingError::NotReceiptHolder,
+    )]
+    pub receipt: Box<Account<'info, TokenAccount>>,
+
+    pub receipt_token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        constraint = token_interface::mint(&to)? == pool.mint @ StakingError::InvalidMint,
+    )]
+    /// CHECK: token account of mint
+    pub to: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = token_interface::mint(&reward_to)? == pool.reward_mint @ StakingError::InvalidMint,
+        constraint = user.allows_reward_to(&reward_to.key(), &authority.key()) @ StakingError::InvalidRewardDestination,
+    )]
+    /// CHECK: token account of reward_mint
+    pub reward_to: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = pool.penalty_destination(),
+        constraint = token_interface::mint(&penalty_to)? == pool.mint @ StakingError::InvalidMint,
+    )]
+    /// CHECK: treasury or reward vault
+    pub penalty_to: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), pool.key().as_ref()],
+        bump = pool.vault_bump,
+    )]
+    /// CHECK: the vault PDA
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"reward".as_ref(), pool.key().as_ref()],
+        bump = pool.reward_vault_bump,
+    )]
+    /// CHECK: the reward vault PDA
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// CHECK: pool mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: pool reward mint
+    pub reward_mint: UncheckedAccount<'info>,
+
+    /// CHECK: pool token program
+    pub token_program: UncheckedAccount<'info>,
+
+    /// CHECK: pool reward token program
+    pub reward_token_program: UncheckedAccount<'info>,
+}
+
+impl EarlyUnstake<'_> {
+    /// Transfer stake minus penalty to user and penalty to treasury, returns the fee withheld from user amount
+    pub fn transfer_from_vault(&self, amount: u64, penalty: u64) -> Result<u64> {
+        let key = self.pool.key();
+        let seeds = [key.as_ref(), &[self.pool.bump]];
+
+        if penalty > 0 {
+            token_interface::transfer_checked(
+                &self.token_program,
+                &self.vault,
+                &self.mint,
+                &self.penalty_to,
+                &self.pool_signer,
+                penalty,
+                &[&seeds],
+            )?;
+        }
+
+        token_interface::transfer_checked(
+            &self.token_program,
+            &self.vault,
+            &self.mint,
+            &self.to,
+            &self.pool_signer,
+            amount,
+            &[&seeds],
+        )?;
+
+        token_interface::transfer_fee(&self.mint, amount)
+    }
+
+    /// Transfer reward from reward vault
+    pub fn transfer_reward(&self, amount: u64) -> Result<()> {
+        let key = self.pool.key();
+        let seeds = [key.as_ref(), &[self.pool.bump]];
+
+        token_interface::transfer_checked(
+            &self.reward_token_program,
+            &self.reward_vault,
+            &self.reward_mint,
+            &self.reward_to,
+            &self.pool_signer,
+            amount,
+            &[&seeds],
+        )
+    }
+
+    /// Burn the position receipt and close holder's receipt token account
+    pub fn burn_receipt(&self) -> Result<()> {
+        token_interface::burn_receipt(
+            &self.receipt_token_program,
+            &self.receipt_mint,
+            &self.receipt.to_account_info(),
+            &self.authority,
+        )
+    }
+}
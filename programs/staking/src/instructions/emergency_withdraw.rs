@@ -0,0 +1,121 @@
+use crate::state::*;
+use crate::token_interface;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::StakingError;
+
+#[derive(Accounts)]
+pub struct EmergencyWithdraw<'info> {
+    #[account(
+        mut,
+        has_one = vault,
+        has_one = mint,
+        has_one = token_program,
+        constraint = pool.emergency @ StakingError::EmergencyNotEnabled,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [
+            pool.key().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    /// CHECK: only for key()
+    pub pool_signer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = pool,
+        seeds = [
+            pool.key().as_ref(),
+            user.authority.as_ref(),
+        ],
+        bump = user.bump,
+    )]
+    pub user: Account<'info, User>,
+
+    #[account(
+        mut,
+        close = authority,
+        has_one = user,
+        seeds = [
+            user.key().as_ref(),
+            position.index.to_le_bytes().as_ref(),
+        ],
+        bump = position.bump,
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"receipt".as_ref(), position.key().as_ref()],
+        bump = position.receipt_bump,
+    )]
+    /// CHECK: the position receipt mint PDA
+    pub receipt_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = holds_receipt(&receipt, &receipt_mint.key(), &authority.key()) @ StakingError::NotReceiptHolder,
+    )]
+    pub receipt: Box<Account<'info, TokenAccount>>,
+
+    pub receipt_token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        constraint = token_interface::mint(&to)? == pool.mint @ StakingError::InvalidMint,
+    )]
+    /// CHECK: token account of mint
+    pub to: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), pool.key().as_ref()],
+        bump = pool.vault_bump,
+    )]
+    /// CHECK: the vault PDA
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: pool mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: pool token program
+    pub token_program: UncheckedAccount<'info>,
+}
+
+impl EmergencyWithdraw<'_> {
+    /// Transfer stake from vault, returns the fee withheld from amount
+    pub fn transfer_from_vault(&self, amount: u64) -> Result<u64> {
+        let key = self.pool.key();
+        let seeds = [key.as_ref(), &[self.pool.bump]];
+
+        token_interface::transfer_checked(
+            &self.token_program,
+            &self.vault,
+            &self.mint,
+            &self.to,
+            &self.pool_signer,
+            amount,
+            &[&seeds],
+        )?;
+
+        token_interface::transfer_fee(&self.mint, amount)
+    }
+
+    /// Burn the position receipt and close holder's receipt token account
+    pub fn burn_receipt(&self) -> Result<()> {
+        token_interface::burn_receipt(
+            &self.receipt_token_program,
+            &self.receipt_mint,
+            &self.receipt.to_account_info(),
+            &self.authority,
+        )
+    }
+}
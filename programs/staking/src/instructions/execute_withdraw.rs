@@ -0,0 +1,144 @@
+use crate::errors::StakingError;
+use crate::state::*;
+use crate::token_interface;
+use crate::ID;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+
+#[derive(Accounts)]
+pub struct ExecuteWithdraw<'info> {
+    #[account(
+        constraint = pool.role_key(Role::Treasurer) == authority.key() @ StakingError::InvalidRole,
+        has_one = reward_vault,
+        has_one = vault,
+        has_one = mint,
+        has_one = reward_mint,
+        has_one = token_program,
+        has_one = reward_token_program,
+        constraint = !pool.paused @ StakingError::PoolPaused,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [
+            pool.key().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    /// CHECK: only for key()
+    pub pool_signer: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = authority,
+        has_one = pool,
+        has_one = to,
+        seeds = [
+            b"withdrawal".as_ref(),
+            pool.key().as_ref(),
+            withdrawal.index.to_le_bytes().as_ref(),
+        ],
+        bump = withdrawal.bump,
+    )]
+    pub withdrawal: Account<'info, Withdrawal>,
+
+    #[account(mut)]
+    /// CHECK: destination token account of withdrawal
+    pub to: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), pool.key().as_ref()],
+        bump = pool.vault_bump,
+    )]
+    /// CHECK: the vault PDA
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"reward".as_ref(), pool.key().as_ref()],
+        bump = pool.reward_vault_bump,
+    )]
+    /// CHECK: the reward vault PDA
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// CHECK: pool mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: pool reward mint
+    pub reward_mint: UncheckedAccount<'info>,
+
+    /// CHECK: pool token program
+    pub token_program: UncheckedAccount<'info>,
+
+    /// CHECK: pool reward token program
+    pub reward_token_program: UncheckedAccount<'info>,
+}
+
+impl ExecuteWithdraw<'_> {
+    /// Transfer queued amount from the vault, only extra (total - required) can be withdrawn
+    pub fn withdraw(&self, now: u64) -> Result<()> {
+        self.pool.check_withdraw(self.withdrawal.vault, now)?;
+
+        let key = self.pool.key();
+        let seeds = [key.as_ref(), &[self.pool.bump]];
+
+        let (token_program, vault, mint) = match self.withdrawal.vault {
+            VaultKind::Stake => (&self.token_program, &self.vault, &self.mint),
+            VaultKind::Reward => (
+                &self.reward_token_program,
+                &self.reward_vault,
+                &self.reward_mint,
+            ),
+        };
+
+        let amount = self.withdrawal.amount;
+        require!(
+            token_interface::amount(vault)?
+                .saturating_sub(self.pool.vault_required(self.withdrawal.vault))
+                >= amount,
+            StakingError::OnlyExtraWithdrawAllowed
+        );
+
+        token_interface::transfer_checked(
+            token_program,
+            vault,
+            mint,
+            &self.to,
+            &self.pool_signer,
+            amount,
+            &[&seeds],
+        )
+    }
+}
+
+pub fn execute_withdraw(
+    pool: Pubkey,
+    pool_account: &Pool,
+    authority: Pubkey,
+    withdrawal: Pubkey,
+    to: Pubkey,
+) -> Instruction {
+    let (pool_signer, _) = Pubkey::find_program_address(&[pool.as_ref()], &ID);
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new_readonly(pool, false),
+            AccountMeta::new_readonly(pool_signer, false),
+            AccountMeta::new(authority, true),
+            AccountMeta::new(withdrawal, false),
+            AccountMeta::new(to, false),
+            AccountMeta::new(pool_account.vault, false),
+            AccountMeta::new(pool_account.reward_vault, false),
+            AccountMeta::new_readonly(pool_account.mint, false),
+            AccountMeta::new_readonly(pool_account.reward_mint, false),
+            AccountMeta::new_readonly(pool_account.token_program, false),
+            AccountMeta::new_readonly(pool_account.reward_token_program, false),
+        ],
+        data: crate::instruction::ExecuteWithdraw.data(),
+    }
+}
@@ -1,21 +1,25 @@
 use crate::errors::StakingError;
 use crate::state::*;
+use crate::token_interface;
 use crate::ID;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::Instruction;
 use anchor_lang::InstructionData;
-use anchor_spl::token::{Token, TokenAccount};
 
 #[derive(Accounts)]
 pub struct FreePool<'info> {
     #[account(
         mut,
         close = receiver,
-        has_one = authority,
+        constraint = pool.role_key(Role::Treasurer) == authority.key() @ StakingError::InvalidRole,
         has_one = vault,
         has_one = reward_vault,
+        has_one = mint,
+        has_one = reward_mint,
+        has_one = token_program,
+        has_one = reward_token_program,
         constraint = !pool.paused @ StakingError::PoolPaused,
-        constraint = pool.closed @ StakingError::PoolHasToBeClosed,
+        constraint = pool.is_closed(Clock::get()?.unix_timestamp as u64) @ StakingError::PoolHasToBeClosed,
     )]
     pub pool: Account<'info, Pool>,
 
@@ -34,36 +38,89 @@ pub struct FreePool<'info> {
         mut,
         seeds = [b"vault".as_ref(), pool.key().as_ref()],
         bump = pool.vault_bump,
+        constraint = token_interface::mint(&vault)? == pool.mint @ StakingError::InvalidMint,
     )]
-    pub vault: Account<'info, TokenAccount>,
+    /// CHECK: the vault PDA
+    pub vault: UncheckedAccount<'info>,
 
     #[account(
         mut,
         seeds = [b"reward".as_ref(), pool.key().as_ref()],
         bump = pool.reward_vault_bump,
+        constraint = token_interface::mint(&reward_vault)? == pool.reward_mint @ StakingError::InvalidMint,
     )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    /// CHECK: the reward vault PDA
+    pub reward_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: pool mint
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: pool reward mint
+    pub reward_mint: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub receiver: SystemAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: pool token program
+    pub token_program: UncheckedAccount<'info>,
+
+    /// CHECK: pool reward token program
+    pub reward_token_program: UncheckedAccount<'info>,
+}
+
+impl FreePool<'_> {
+    pub fn close_vaults(&self) -> Result<()> {
+        let key = self.pool.key();
+        let seeds = [key.as_ref(), &[self.pool.bump]];
+
+        // Close vault
+        token_interface::harvest_withheld_fees(&self.token_program, &self.mint, &self.vault)?;
+        token_interface::close_account(
+            &self.token_program,
+            &self.vault,
+            &self.receiver,
+            &self.pool_signer,
+            &[&seeds],
+        )?;
+
+        // Close reward_vault
+        token_interface::harvest_withheld_fees(
+            &self.reward_token_program,
+            &self.reward_mint,
+            &self.reward_vault,
+        )?;
+        token_interface::close_account(
+            &self.reward_token_program,
+            &self.reward_vault,
+            &self.receiver,
+            &self.pool_signer,
+            &[&seeds],
+        )
+    }
 }
 
-pub fn free_pool(pool: Pubkey, authority: Pubkey, destination: Pubkey) -> Instruction {
+pub fn free_pool(
+    pool: Pubkey,
+    pool_account: &Pool,
+    authority: Pubkey,
+    destination: Pubkey,
+) -> Instruction {
     let (pool_signer, _) = Pubkey::find_program_address(&[pool.as_ref()], &ID);
-    let (vault, _) = Pubkey::find_program_address(&[b"vault".as_ref(), pool.as_ref()], &ID);
-    let (reward_vault, _) = Pubkey::find_program_address(&[b"reward".as_ref(), pool.as_ref()], &ID);
     Instruction {
         program_id: ID,
         accounts: vec![
             AccountMeta::new(pool, false),
             AccountMeta::new_readonly(authority, true),
             AccountMeta::new_readonly(pool_signer, false),
-            AccountMeta::new(vault, false),
-            AccountMeta::new(reward_vault, false),
+            AccountMeta::new(pool_account.vault, false),
+            AccountMeta::new(pool_account.reward_vault, false),
+            AccountMeta::new(pool_account.mint, false),
+            AccountMeta::new(pool_account.reward_mint, false),
             AccountMeta::new(destination, false),
-            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            AccountMeta::new_readonly(pool_account.token_program, false),
+            AccountMeta::new_readonly(pool_account.reward_token_program, false),
         ],
         data: crate::instruction::FreePool.data(),
     }
@@ -8,9 +8,9 @@ use anchor_lang::InstructionData;
 #[derive(Accounts)]
 pub struct FreeUser<'info> {
     #[account(
-        has_one = authority,
+        constraint = pool.role_key(Role::Treasurer) == authority.key() @ StakingError::InvalidRole,
         constraint = !pool.paused @ StakingError::PoolPaused,
-        constraint = pool.closed @ StakingError::PoolHasToBeClosed,
+        constraint = pool.is_closed(Clock::get()?.unix_timestamp as u64) @ StakingError::PoolHasToBeClosed,
     )]
     pub pool: Account<'info, Pool>,
 
@@ -0,0 +1,97 @@
+use crate::state::*;
+use crate::token_interface;
+use crate::ID;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+
+use crate::errors::StakingError;
+
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    #[account(
+        mut,
+        has_one = reward_vault,
+        has_one = reward_mint,
+        has_one = reward_token_program,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = token_interface::mint(&from)? == pool.reward_mint @ StakingError::InvalidMint,
+    )]
+    /// CHECK: token account of reward_mint
+    pub from: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"reward".as_ref(), pool.key().as_ref()],
+        bump = pool.reward_vault_bump,
+    )]
+    /// CHECK: the reward vault PDA
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// CHECK: pool reward mint
+    pub reward_mint: UncheckedAccount<'info>,
+
+    /// CHECK: pool reward token program
+    pub reward_token_program: UncheckedAccount<'info>,
+}
+
+impl FundRewards<'_> {
+    /// Transfer reward to reward vault, returns the amount received by reward vault
+    pub fn transfer_to_reward_vault(&self, amount: u64) -> Result<u64> {
+        let before = token_interface::amount(&self.reward_vault)?;
+
+        token_interface::transfer_checked(
+            &self.reward_token_program,
+            &self.from,
+            &self.reward_mint,
+            &self.reward_vault,
+            &self.funder,
+            amount,
+            &[],
+        )?;
+
+        let after = token_interface::amount(&self.reward_vault)?;
+        Ok(after - before)
+    }
+
+    /// With funding cap check reward vault doesn't exceed the total payable reward
+    pub fn check_funding_cap(&self) -> Result<()> {
+        if !self.pool.funding_cap {
+            return Ok(());
+        }
+
+        require!(
+            token_interface::amount(&self.reward_vault)? <= self.pool.max_payable()?,
+            StakingError::FundingCapExceeded
+        );
+        Ok(())
+    }
+}
+
+pub fn fund_rewards(
+    pool: Pubkey,
+    pool_account: &Pool,
+    funder: Pubkey,
+    from: Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(pool, false),
+            AccountMeta::new_readonly(funder, true),
+            AccountMeta::new(from, false),
+            AccountMeta::new(pool_account.reward_vault, false),
+            AccountMeta::new_readonly(pool_account.reward_mint, false),
+            AccountMeta::new_readonly(pool_account.reward_token_program, false),
+        ],
+        data: crate::instruction::FundRewards { amount }.data(),
+    }
+}
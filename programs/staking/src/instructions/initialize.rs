@@ -1,20 +1,17 @@
+use crate::errors::StakingError;
 use crate::state::*;
+use crate::token_interface;
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
-
-#[cfg(not(feature = "mock-mint"))]
-pub const TOKEN_MINT_PUBKEY: &str = "ZfnjRUKtc5vweE1GCLdHV4MkDQ3ebSpQXLobSKgQ9RB1";
-
-#[cfg(feature = "mock-mint")]
-pub const TOKEN_MINT_PUBKEY: &str = "CxEgZaGFN1eSezxiaKsVdUm4LJVBTi4weCmVReiGbPLA";
+use std::collections::BTreeMap;
 
 #[derive(Accounts)]
+#[instruction(tiers: Vec<RewardTier>)]
 pub struct Initialize<'info> {
     #[account(
         init,
         payer = payer,
-        space = Pool::space(),
+        space = Pool::space(tiers.len()),
     )]
     pub pool: Box<Account<'info, Pool>>,
 
@@ -30,33 +27,93 @@ pub struct Initialize<'info> {
     /// CHECK: only for key()
     pub pool_signer: UncheckedAccount<'info>,
 
-    #[account(address = TOKEN_MINT_PUBKEY.parse::<Pubkey>().unwrap())]
-    pub mint: Account<'info, Mint>,
+    #[account(constraint = token_interface::is_token_program(mint.owner) @ StakingError::InvalidMint)]
+    /// CHECK: SPL Token or Token-2022 mint
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(constraint = token_interface::is_token_program(reward_mint.owner) @ StakingError::InvalidMint)]
+    /// CHECK: SPL Token or Token-2022 mint
+    pub reward_mint: UncheckedAccount<'info>,
 
     #[account(
-        init,
-        payer = payer,
+        seeds = [b"allowlist".as_ref()],
+        bump,
+    )]
+    /// CHECK: restricts mints only if initialized
+    pub allowlist: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
         seeds = [b"vault".as_ref(), pool.key().as_ref()],
         bump,
-        token::mint = mint,
-        token::authority = pool_signer,
     )]
-    pub vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: created as token account of mint
+    pub vault: UncheckedAccount<'info>,
 
     #[account(
-        init,
-        payer = payer,
+        mut,
         seeds = [b"reward".as_ref(), pool.key().as_ref()],
         bump,
-        token::mint = mint,
-        token::authority = pool_signer,
     )]
-    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: created as token account of reward_mint
+    pub reward_vault: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(address = *mint.owner)]
+    /// CHECK: owner of mint
+    pub token_program: UncheckedAccount<'info>,
+    #[account(address = *reward_mint.owner)]
+    /// CHECK: owner of reward_mint
+    pub reward_token_program: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
+
+impl Initialize<'_> {
+    pub fn check_mints(&self) -> Result<()> {
+        if self.allowlist.data_is_empty() {
+            return Ok(());
+        }
+
+        let allowlist: Account<Allowlist> = Account::try_from(&self.allowlist)?;
+        require!(
+            allowlist.allows(&self.mint.key()) && allowlist.allows(&self.reward_mint.key()),
+            StakingError::MintNotAllowed
+        );
+        Ok(())
+    }
+
+    pub fn create_vaults(&self, bumps: &BTreeMap<String, u8>) -> Result<()> {
+        let key = self.pool.key();
+        let vault_bump = *bumps
+            .get("vault")
+            .ok_or_else(|| error!(StakingError::BumpFailure))?;
+        let reward_vault_bump = *bumps
+            .get("reward_vault")
+            .ok_or_else(|| error!(StakingError::BumpFailure))?;
+
+        token_interface::create_account(
+            &self.token_program,
+            &self.payer,
+            &self.vault,
+            &self.mint,
+            &self.pool_signer,
+            &self.system_program,
+            &self.rent,
+            &[&[b"vault".as_ref(), key.as_ref(), &[vault_bump]]],
+        )?;
+
+        token_interface::create_account(
+            &self.reward_token_program,
+            &self.payer,
+            &self.reward_vault,
+            &self.reward_mint,
+            &self.pool_signer,
+            &self.system_program,
+            &self.rent,
+            &[&[b"reward".as_ref(), key.as_ref(), &[reward_vault_bump]]],
+        )
+    }
+}
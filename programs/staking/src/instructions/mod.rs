@@ -1,25 +1,75 @@
+pub mod accept_authority;
+pub mod cancel_withdraw;
 pub mod claim;
 pub mod close;
+pub mod close_allowlist;
+pub mod create_allowlist;
 pub mod create_user;
+pub mod early_unstake;
+pub mod emergency_withdraw;
+pub mod enable_emergency;
+pub mod execute_withdraw;
+pub mod exit;
 pub mod free_pool;
 pub mod free_user;
+pub mod fund_rewards;
 pub mod initialize;
 pub mod open;
 pub mod pause;
+pub mod pending_rewards;
+pub mod pool_summary;
+pub mod propose_authority;
+pub mod queue_withdraw;
+pub mod set_allowlist;
+pub mod set_delegate;
+pub mod set_funding_cap;
+pub mod set_merkle_root;
+pub mod set_role;
+pub mod set_solvency_check;
+pub mod set_staking_window;
+pub mod set_treasury;
+pub mod set_withdraw_delay;
 pub mod stake;
+pub mod transfer_position;
 pub mod unpause;
 pub mod unstake;
+pub mod update_tier;
 pub mod withdraw;
 
+pub use accept_authority::*;
+pub use cancel_withdraw::*;
 pub use claim::*;
 pub use close::*;
+pub use close_allowlist::*;
+pub use create_allowlist::*;
 pub use create_user::*;
+pub use early_unstake::*;
+pub use emergency_withdraw::*;
+pub use enable_emergency::*;
+pub use execute_withdraw::*;
+pub use exit::*;
 pub use free_pool::*;
 pub use free_user::*;
+pub use fund_rewards::*;
 pub use initialize::*;
 pub use open::*;
 pub use pause::*;
+pub use pending_rewards::*;
+pub use pool_summary::*;
+pub use propose_authority::*;
+pub use queue_withdraw::*;
+pub use set_allowlist::*;
+pub use set_delegate::*;
+pub use set_funding_cap::*;
+pub use set_merkle_root::*;
+pub use set_role::*;
+pub use set_solvency_check::*;
+pub use set_staking_window::*;
+pub use set_treasury::*;
+pub use set_withdraw_delay::*;
 pub use stake::*;
+pub use transfer_position::*;
 pub use unpause::*;
 pub use unstake::*;
+pub use update_tier::*;
 pub use withdraw::*;
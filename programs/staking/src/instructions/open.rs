@@ -10,9 +10,10 @@ use crate::errors::StakingError;
 pub struct OpenPool<'info> {
     #[account(
         mut,
-        has_one = authority,
+        constraint = pool.role_key(Role::Operator) == authority.key() @ StakingError::InvalidRole,
         constraint = !pool.paused @ StakingError::PoolPaused,
         constraint = pool.closed,
+        constraint = !pool.emergency @ StakingError::EmergencyMode,
     )]
     pub pool: Account<'info, Pool>,
 
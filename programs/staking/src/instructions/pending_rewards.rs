@@ -0,0 +1,89 @@
+use crate::errors::StakingError;
+use crate::state::*;
+use crate::ID;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+
+#[derive(Accounts)]
+pub struct PendingRewards<'info> {
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        has_one = pool,
+        seeds = [
+            pool.key().as_ref(),
+            user.authority.as_ref(),
+        ],
+        bump = user.bump,
+    )]
+    pub user: Account<'info, User>,
+}
+
+/// Reward of position which can be claimed at the moment
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct PositionReward {
+    pub position: Pubkey,
+    pub tier: u8,
+    /// Vested reward which is not claimed yet
+    pub amount: u64,
+    /// The time lock has passed, position can be unstaked
+    pub unlocked: bool,
+}
+
+/// Pending rewards of user positions, returned by `pending_rewards`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct UserRewards {
+    pub user: Pubkey,
+    /// Sum of position rewards
+    pub total: u64,
+    pub positions: Vec<PositionReward>,
+}
+
+impl PendingRewards<'_> {
+    /// Calc claimable reward of positions without updating them
+    pub fn rewards(&self, positions: &[AccountInfo], now: u64) -> Result<UserRewards> {
+        let user = self.user.key();
+        let mut rewards: Vec<PositionReward> = Vec::with_capacity(positions.len());
+        for info in positions {
+            let position = Account::<Position>::try_from(info)?;
+            require!(
+                position.user == user && rewards.iter().all(|r| r.position != position.key()),
+                StakingError::InvalidPosition
+            );
+
+            let tier = &self.pool.tiers[position.tier as usize];
+            let (amount, status) = position.status.update_reword(tier, now);
+            rewards.push(PositionReward {
+                position: position.key(),
+                tier: position.tier,
+                amount,
+                unlocked: matches!(status, StakeStatus::Ready { .. }),
+            });
+        }
+
+        Ok(UserRewards {
+            user,
+            total: rewards.iter().map(|reward| reward.amount).sum(),
+            positions: rewards,
+        })
+    }
+}
+
+pub fn pending_rewards(pool: Pubkey, user: Pubkey, positions: &[Pubkey]) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(pool, false),
+        AccountMeta::new_readonly(user, false),
+    ];
+    accounts.extend(
+        positions
+            .iter()
+            .map(|position| AccountMeta::new_readonly(*position, false)),
+    );
+
+    Instruction {
+        program_id: ID,
+        accounts,
+        data: crate::instruction::PendingRewards.data(),
+    }
+}
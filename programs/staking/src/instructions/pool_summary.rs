@@ -0,0 +1,70 @@
+use crate::state::*;
+use crate::token_interface;
+use crate::ID;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+
+#[derive(Accounts)]
+pub struct PoolSummary<'info> {
+    #[account(
+        has_one = vault,
+        has_one = reward_vault,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// CHECK: the vault PDA
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: the reward vault PDA
+    pub reward_vault: UncheckedAccount<'info>,
+}
+
+/// State of pool and its vaults, returned by `pool_summary`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct PoolStats {
+    /// Pool accepts new stakes at the moment
+    pub open: bool,
+    pub paused: bool,
+    pub emergency: bool,
+    pub metrics: Metrics,
+    pub vault_amount: u64,
+    pub reward_vault_amount: u64,
+    /// Reward vault amount above the unpaid reward, can be withdrawn
+    pub extra_reward: u64,
+    /// Unpaid reward and max reward of all available slots
+    pub max_payable: u64,
+    /// Available slots of tiers
+    pub slots: Vec<u16>,
+}
+
+impl PoolSummary<'_> {
+    pub fn stats(&self, now: u64) -> Result<PoolStats> {
+        let pool = &self.pool;
+        let reward_vault_amount = token_interface::amount(&self.reward_vault)?;
+
+        Ok(PoolStats {
+            open: !pool.paused && pool.check_open(now).is_ok(),
+            paused: pool.paused,
+            emergency: pool.emergency,
+            metrics: pool.metrics,
+            vault_amount: token_interface::amount(&self.vault)?,
+            reward_vault_amount,
+            extra_reward: reward_vault_amount.saturating_sub(pool.metrics.unpaid_reward()),
+            max_payable: pool.max_payable()?,
+            slots: pool.tiers.iter().map(|tier| tier.slots).collect(),
+        })
+    }
+}
+
+pub fn pool_summary(pool: Pubkey, pool_account: &Pool) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new_readonly(pool, false),
+            AccountMeta::new_readonly(pool_account.vault, false),
+            AccountMeta::new_readonly(pool_account.reward_vault, false),
+        ],
+        data: crate::instruction::PoolSummary.data(),
+    }
+}
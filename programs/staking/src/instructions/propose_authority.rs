@@ -0,0 +1,31 @@
+use crate::state::*;
+use crate::ID;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn propose_authority(
+    pool: Pubkey,
+    authority: Pubkey,
+    pending_authority: Option<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(pool, false),
+            AccountMeta::new_readonly(authority, true),
+        ],
+        data: crate::instruction::ProposeAuthority { pending_authority }.data(),
+    }
+}
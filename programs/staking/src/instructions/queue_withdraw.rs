@@ -0,0 +1,79 @@
+use crate::errors::StakingError;
+use crate::state::*;
+use crate::token_interface;
+use crate::ID;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::InstructionData;
+
+#[derive(Accounts)]
+#[instruction(vault: VaultKind)]
+pub struct QueueWithdraw<'info> {
+    #[account(
+        mut,
+        constraint = pool.role_key(Role::Treasurer) == authority.key() @ StakingError::InvalidRole,
+        constraint = !pool.paused @ StakingError::PoolPaused,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Withdrawal::space(),
+        seeds = [
+            b"withdrawal".as_ref(),
+            pool.key().as_ref(),
+            pool.next_withdrawal.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub withdrawal: Account<'info, Withdrawal>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = token_interface::mint(&to)? == pool.vault_mint(vault) @ StakingError::InvalidMint,
+    )]
+    /// CHECK: token account of withdrawn vault mint
+    pub to: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn withdrawal_address(pool: Pubkey, index: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"withdrawal".as_ref(),
+            pool.as_ref(),
+            index.to_le_bytes().as_ref(),
+        ],
+        &ID,
+    )
+    .0
+}
+
+pub fn queue_withdraw(
+    pool: Pubkey,
+    pool_account: &Pool,
+    authority: Pubkey,
+    to: Pubkey,
+    vault: VaultKind,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(pool, false),
+            AccountMeta::new(
+                withdrawal_address(pool, pool_account.next_withdrawal),
+                false,
+            ),
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(to, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: crate::instruction::QueueWithdraw { vault, amount }.data(),
+    }
+}
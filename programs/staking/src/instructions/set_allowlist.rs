@@ -0,0 +1,16 @@
+use crate::state::*;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetAllowlist<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [b"allowlist".as_ref()],
+        bump = allowlist.bump,
+    )]
+    pub allowlist: Account<'info, Allowlist>,
+
+    pub authority: Signer<'info>,
+}
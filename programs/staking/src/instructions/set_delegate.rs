@@ -0,0 +1,22 @@
+use crate::state::*;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        has_one = pool,
+        seeds = [
+            pool.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump = user.bump,
+    )]
+    pub user: Account<'info, User>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,30 @@
+use crate::state::*;
+use crate::ID;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+
+use crate::errors::StakingError;
+
+#[derive(Accounts)]
+pub struct SetSolvencyCheck<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        constraint = !pool.paused @ StakingError::PoolPaused,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_solvency_check(pool: Pubkey, authority: Pubkey, enabled: bool) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(pool, false),
+            AccountMeta::new_readonly(authority, true),
+        ],
+        data: crate::instruction::SetSolvencyCheck { enabled }.data(),
+    }
+}
@@ -0,0 +1,39 @@
+use crate::state::*;
+use crate::ID;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+
+use crate::errors::StakingError;
+
+#[derive(Accounts)]
+pub struct SetStakingWindow<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        constraint = !pool.paused @ StakingError::PoolPaused,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_staking_window(
+    pool: Pubkey,
+    authority: Pubkey,
+    opens_at: Option<u64>,
+    closes_at: Option<u64>,
+) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(pool, false),
+            AccountMeta::new_readonly(authority, true),
+        ],
+        data: crate::instruction::SetStakingWindow {
+            opens_at,
+            closes_at,
+        }
+        .data(),
+    }
+}
@@ -0,0 +1,36 @@
+use crate::state::*;
+use crate::token_interface;
+use crate::ID;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+
+use crate::errors::StakingError;
+
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        constraint = !pool.paused @ StakingError::PoolPaused,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub authority: Signer<'info>,
+
+    #[account(constraint = token_interface::mint(&treasury)? == pool.mint @ StakingError::InvalidMint)]
+    /// CHECK: token account of mint
+    pub treasury: UncheckedAccount<'info>,
+}
+
+pub fn set_treasury(pool: Pubkey, authority: Pubkey, treasury: Pubkey) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(pool, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(treasury, false),
+        ],
+        data: crate::instruction::SetTreasury.data(),
+    }
+}
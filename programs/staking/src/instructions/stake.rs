@@ -1,20 +1,36 @@
 use crate::state::*;
+use crate::token_interface;
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_spl::token::Token;
 
 use crate::errors::StakingError;
 
 #[derive(Accounts)]
+#[instruction(tier: u8)]
 pub struct Stake<'info> {
     #[account(
         mut,
         has_one = vault,
+        has_one = reward_vault,
+        has_one = mint,
+        has_one = token_program,
         constraint = !pool.paused @ StakingError::PoolPaused,
         constraint = !pool.closed @ StakingError::PoolClosed,
+        constraint = (tier as usize) < pool.tiers.len() @ StakingError::InvalidTier,
     )]
     pub pool: Account<'info, Pool>,
 
+    #[account(
+        seeds = [
+            pool.key().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    /// CHECK: only for key()
+    pub pool_signer: UncheckedAccount<'info>,
+
     #[account(
         mut,
         has_one = authority,
@@ -27,29 +43,129 @@ pub struct Stake<'info> {
     )]
     pub user: Account<'info, User>,
 
-    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = Position::space(),
+        seeds = [
+            user.key().as_ref(),
+            user.next_position.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub position: Account<'info, Position>,
 
     #[account(mut)]
-    pub from: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"receipt".as_ref(), position.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: receipt mint PDA, created in instruction
+    pub receipt_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = get_associated_token_address(&authority.key(), &receipt_mint.key()),
+    )]
+    /// CHECK: associated token account of receipt, created in instruction
+    pub receipt: UncheckedAccount<'info>,
+
+    pub receipt_token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(
+        mut,
+        constraint = token_interface::mint(&from)? == pool.mint @ StakingError::InvalidMint,
+    )]
+    /// CHECK: token account of mint
+    pub from: UncheckedAccount<'info>,
 
     #[account(
         mut,
         seeds = [b"vault".as_ref(), pool.key().as_ref()],
         bump = pool.vault_bump,
     )]
-    pub vault: Account<'info, TokenAccount>,
+    /// CHECK: the vault PDA
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"reward".as_ref(), pool.key().as_ref()],
+        bump = pool.reward_vault_bump,
+    )]
+    /// CHECK: the reward vault PDA
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// CHECK: pool mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: pool token program
+    pub token_program: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
-impl<'a, 'b, 'c, 'info> From<&mut Stake<'info>> for CpiContext<'a, 'b, 'c, 'info, Transfer<'info>> {
-    fn from(accounts: &mut Stake<'info>) -> CpiContext<'a, 'b, 'c, 'info, Transfer<'info>> {
-        let cpi_accounts = Transfer {
-            from: accounts.from.to_account_info(),
-            to: accounts.vault.to_account_info(),
-            authority: accounts.authority.to_account_info(),
-        };
-        let cpi_program = accounts.token_program.to_account_info();
-        CpiContext::new(cpi_program, cpi_accounts)
+impl Stake<'_> {
+    /// In solvency mode check reward vault covers unpaid rewards including reward of the stake
+    pub fn check_solvency(&self, reward_tier: &RewardTier, stake: u64) -> Result<()> {
+        if !self.pool.solvency_check {
+            return Ok(());
+        }
+
+        let fee = token_interface::transfer_fee(&self.mint, stake)?;
+        let reward = reward_tier.reward(stake - fee)?;
+        let required = self
+            .pool
+            .metrics
+            .unpaid_reward()
+            .checked_add(reward)
+            .ok_or_else(|| error!(StakingError::CalcFailure))?;
+
+        require!(
+            token_interface::amount(&self.reward_vault)? >= required,
+            StakingError::InsufficientRewardFunds
+        );
+        Ok(())
+    }
+
+    /// Transfer stake to vault, returns the amount received by vault
+    pub fn transfer_to_vault(&self, amount: u64) -> Result<u64> {
+        let before = token_interface::amount(&self.vault)?;
+
+        token_interface::transfer_checked(
+            &self.token_program,
+            &self.from,
+            &self.mint,
+            &self.vault,
+            &self.authority,
+            amount,
+            &[],
+        )?;
+
+        let after = token_interface::amount(&self.vault)?;
+        Ok(after - before)
+    }
+
+    /// Mint receipt of the position to authority
+    pub fn create_receipt(&self, receipt_bump: u8) -> Result<()> {
+        let pool = self.pool.key();
+        let position = self.position.key();
+
+        token_interface::create_receipt(
+            &self.receipt_token_program,
+            &self.associated_token_program,
+            &self.authority,
+            &self.receipt_mint,
+            &self.receipt,
+            &self.authority,
+            &self.pool_signer,
+            &self.system_program,
+            &self.rent,
+            &[b"receipt".as_ref(), position.as_ref(), &[receipt_bump]],
+            &[pool.as_ref(), &[self.pool.bump]],
+        )
     }
 }
@@ -0,0 +1,146 @@
+use crate::state::*;
+use crate::token_interface;
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::StakingError;
+
+#[derive(Accounts)]
+pub struct TransferPosition<'info> {
+    #[account(
+        constraint = !pool.paused @ StakingError::PoolPaused,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        has_one = pool,
+        seeds = [
+            pool.key().as_ref(),
+            user.authority.as_ref(),
+        ],
+        bump = user.bump,
+    )]
+    pub user: Box<Account<'info, User>>,
+
+    #[account(
+        mut,
+        close = authority,
+        has_one = user,
+        seeds = [
+            user.key().as_ref(),
+            position.index.to_le_bytes().as_ref(),
+        ],
+        bump = position.bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"receipt".as_ref(), position.key().as_ref()],
+        bump = position.receipt_bump,
+    )]
+    /// CHECK: the position receipt mint PDA
+    pub receipt_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = holds_receipt(&receipt, &receipt_mint.key(), &authority.key()) @ StakingError::NotReceiptHolder,
+    )]
+    pub receipt: Box<Account<'info, TokenAccount>>,
+
+    pub receipt_token_program: Program<'info, Token>,
+
+    #[account(constraint = recipient.key() != user.authority @ StakingError::InvalidRecipient)]
+    /// CHECK: wallet receiving the position
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = User::space(pool.tiers.len()),
+        seeds = [
+            pool.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub recipient_user: Box<Account<'info, User>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Position::space(),
+        seeds = [
+            recipient_user.key().as_ref(),
+            recipient_user.next_position.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub recipient_position: Box<Account<'info, Position>>,
+
+    #[account(
+        mut,
+        seeds = [b"receipt".as_ref(), recipient_position.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: receipt mint PDA of the recipient position, created in instruction
+    pub recipient_receipt_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = get_associated_token_address(&recipient.key(), &recipient_receipt_mint.key()),
+    )]
+    /// CHECK: recipient associated token account of receipt, created in instruction
+    pub recipient_receipt: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            pool.key().as_ref()
+        ],
+        bump = pool.bump
+    )]
+    /// CHECK: only for key()
+    pub pool_signer: UncheckedAccount<'info>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl TransferPosition<'_> {
+    /// Burn the position receipt and close holder's receipt token account
+    pub fn burn_receipt(&self) -> Result<()> {
+        token_interface::burn_receipt(
+            &self.receipt_token_program,
+            &self.receipt_mint,
+            &self.receipt.to_account_info(),
+            &self.authority,
+        )
+    }
+
+    /// Mint receipt of the new position to recipient
+    pub fn create_receipt(&self, receipt_bump: u8) -> Result<()> {
+        let pool = self.pool.key();
+        let position = self.recipient_position.key();
+
+        token_interface::create_receipt(
+            &self.receipt_token_program,
+            &self.associated_token_program,
+            &self.authority,
+            &self.recipient_receipt_mint,
+            &self.recipient_receipt,
+            &self.recipient,
+            &self.pool_signer,
+            &self.system_program,
+            &self.rent,
+            &[b"receipt".as_ref(), position.as_ref(), &[receipt_bump]],
+            &[pool.as_ref(), &[self.pool.bump]],
+        )
+    }
+}
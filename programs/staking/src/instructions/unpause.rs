@@ -1,3 +1,4 @@
+use crate::errors::StakingError;
 use crate::state::*;
 use crate::ID;
 use anchor_lang::prelude::*;
@@ -8,7 +9,7 @@ use anchor_lang::InstructionData;
 pub struct Unpause<'info> {
     #[account(
         mut,
-        has_one = authority,
+        constraint = pool.role_key(Role::Operator) == authority.key() @ StakingError::InvalidRole,
         constraint = pool.paused,
     )]
     pub pool: Account<'info, Pool>,
@@ -1,7 +1,8 @@
 use crate::state::*;
+use crate::token_interface;
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer};
+use anchor_spl::token::{Token, TokenAccount};
 
 use crate::errors::StakingError;
 
@@ -10,6 +11,8 @@ pub struct Unstake<'info> {
     #[account(
         mut,
         has_one = vault,
+        has_one = mint,
+        has_one = token_program,
         constraint = !pool.paused @ StakingError::PoolPaused,
     )]
     pub pool: Account<'info, Pool>,
@@ -25,41 +28,95 @@ pub struct Unstake<'info> {
 
     #[account(
         mut,
-        has_one = authority,
         has_one = pool,
         seeds = [
             pool.key().as_ref(),
-            authority.key().as_ref(),
+            user.authority.as_ref(),
         ],
         bump = user.bump,
     )]
     pub user: Account<'info, User>,
 
-    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        close = authority,
+        has_one = user,
+        seeds = [
+            user.key().as_ref(),
+            position.index.to_le_bytes().as_ref(),
+        ],
+        bump = position.bump,
+    )]
+    pub position: Account<'info, Position>,
 
+    /// Receipt holder, the delegate can't withdraw principal
     #[account(mut)]
-    pub to: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"receipt".as_ref(), position.key().as_ref()],
+        bump = position.receipt_bump,
+    )]
+    /// CHECK: the position receipt mint PDA
+    pub receipt_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = holds_receipt(&receipt, &receipt_mint.key(), &authority.key()) @ StakingError::NotReceiptHolder,
+    )]
+    pub receipt: Box<Account<'info, TokenAccount>>,
+
+    pub receipt_token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        constraint = token_interface::mint(&to)? == pool.mint @ StakingError::InvalidMint,
+    )]
+    /// CHECK: token account of mint
+    pub to: UncheckedAccount<'info>,
 
     #[account(
         mut,
         seeds = [b"vault".as_ref(), pool.key().as_ref()],
         bump = pool.vault_bump,
     )]
-    pub vault: Account<'info, TokenAccount>,
+    /// CHECK: the vault PDA
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: pool mint
+    pub mint: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: pool token program
+    pub token_program: UncheckedAccount<'info>,
 }
 
-impl<'a, 'b, 'c, 'info> From<&mut Unstake<'info>>
-    for CpiContext<'a, 'b, 'c, 'info, Transfer<'info>>
-{
-    fn from(accounts: &mut Unstake<'info>) -> CpiContext<'a, 'b, 'c, 'info, Transfer<'info>> {
-        let cpi_accounts = Transfer {
-            from: accounts.vault.to_account_info(),
-            to: accounts.to.to_account_info(),
-            authority: accounts.pool_signer.to_account_info(),
-        };
-        let cpi_program = accounts.token_program.to_account_info();
-        CpiContext::new(cpi_program, cpi_accounts)
+impl Unstake<'_> {
+    /// Transfer stake from vault, returns the fee withheld from amount
+    pub fn transfer_from_vault(&self, amount: u64) -> Result<u64> {
+        let key = self.pool.key();
+        let seeds = [key.as_ref(), &[self.pool.bump]];
+
+        token_interface::transfer_checked(
+            &self.token_program,
+            &self.vault,
+            &self.mint,
+            &self.to,
+            &self.pool_signer,
+            amount,
+            &[&seeds],
+        )?;
+
+        token_interface::transfer_fee(&self.mint, amount)
+    }
+
+    /// Burn the position receipt and close holder's receipt token account
+    pub fn burn_receipt(&self) -> Result<()> {
+        token_interface::burn_receipt(
+            &self.receipt_token_program,
+            &self.receipt_mint,
+            &self.receipt.to_account_info(),
+            &self.authority,
+        )
     }
 }
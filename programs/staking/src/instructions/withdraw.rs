@@ -1,19 +1,23 @@
 use crate::errors::StakingError;
 use crate::state::*;
+use crate::token_interface;
 use crate::ID;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::Instruction;
 use anchor_lang::InstructionData;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
     #[account(
-        has_one = authority,
+        constraint = pool.role_key(Role::Treasurer) == authority.key() @ StakingError::InvalidRole,
         has_one = reward_vault,
         has_one = vault,
+        has_one = mint,
+        has_one = reward_mint,
+        has_one = token_program,
+        has_one = reward_token_program,
         constraint = !pool.paused @ StakingError::PoolPaused,
-        constraint = pool.closed @ StakingError::PoolHasToBeClosed,
+        constraint = pool.withdraw_delay == 0 @ StakingError::WithdrawDelayed,
     )]
     pub pool: Account<'info, Pool>,
 
@@ -28,80 +32,102 @@ pub struct Withdraw<'info> {
 
     pub authority: Signer<'info>,
 
-    #[account(mut)]
-    pub to: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = token_interface::mint(&to)? == pool.mint @ StakingError::InvalidMint,
+    )]
+    /// CHECK: token account of mint
+    pub to: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = token_interface::mint(&reward_to)? == pool.reward_mint @ StakingError::InvalidMint,
+    )]
+    /// CHECK: token account of reward_mint
+    pub reward_to: UncheckedAccount<'info>,
 
     #[account(
         mut,
         seeds = [b"vault".as_ref(), pool.key().as_ref()],
         bump = pool.vault_bump,
+        constraint = token_interface::mint(&vault)? == pool.mint @ StakingError::InvalidMint,
     )]
-    pub vault: Account<'info, TokenAccount>,
+    /// CHECK: the vault PDA
+    pub vault: UncheckedAccount<'info>,
 
     #[account(
         mut,
         seeds = [b"reward".as_ref(), pool.key().as_ref()],
         bump = pool.reward_vault_bump,
+        constraint = token_interface::mint(&reward_vault)? == pool.reward_mint @ StakingError::InvalidMint,
     )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    /// CHECK: the reward vault PDA
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// CHECK: pool mint
+    pub mint: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: pool reward mint
+    pub reward_mint: UncheckedAccount<'info>,
+
+    /// CHECK: pool token program
+    pub token_program: UncheckedAccount<'info>,
+
+    /// CHECK: pool reward token program
+    pub reward_token_program: UncheckedAccount<'info>,
 }
 
 impl Withdraw<'_> {
-    pub fn withdraw_extra(&mut self) -> Result<()> {
+    /// Extra (total - required) amount in vault
+    pub fn extra(&self, vault: VaultKind) -> Result<u64> {
+        let account = match vault {
+            VaultKind::Stake => &self.vault,
+            VaultKind::Reward => &self.reward_vault,
+        };
+        Ok(token_interface::amount(account)?.saturating_sub(self.pool.vault_required(vault)))
+    }
+
+    /// Withdraw amount of extra from vault to destination of the vault mint
+    pub fn withdraw(&self, vault: VaultKind, amount: u64) -> Result<()> {
+        require!(
+            amount <= self.extra(vault)?,
+            StakingError::OnlyExtraWithdrawAllowed
+        );
+
         let key = self.pool.key();
         let seeds = [key.as_ref(), &[self.pool.bump]];
 
-        let extra_rewards = self.reward_vault.amount.saturating_sub(
-            self.pool
-                .metrics
-                .reward_requirements
-                .saturating_sub(self.pool.metrics.reward_paid),
-        );
+        let (token_program, from, mint, to) = match vault {
+            VaultKind::Stake => (&self.token_program, &self.vault, &self.mint, &self.to),
+            VaultKind::Reward => (
+                &self.reward_token_program,
+                &self.reward_vault,
+                &self.reward_mint,
+                &self.reward_to,
+            ),
+        };
+
+        token_interface::transfer_checked(
+            token_program,
+            from,
+            mint,
+            to,
+            &self.pool_signer,
+            amount,
+            &[&seeds],
+        )
+    }
 
+    /// Withdraw extra from both vaults, returns withdrawn amounts of vault and reward vault
+    pub fn withdraw_extra(&mut self) -> Result<(u64, u64)> {
+        let extra_rewards = self.extra(VaultKind::Reward)?;
         if extra_rewards > 0 {
-            let cpi_accounts = Transfer {
-                from: self.reward_vault.to_account_info(),
-                to: self.to.to_account_info(),
-                authority: self.pool_signer.to_account_info(),
-            };
-
-            token::transfer(
-                CpiContext::new_with_signer(
-                    self.token_program.to_account_info(),
-                    cpi_accounts,
-                    &[&seeds],
-                ),
-                extra_rewards,
-            )?;
+            self.withdraw(VaultKind::Reward, extra_rewards)?;
         }
 
-        let expected_vault_amount: u64 = self
-            .pool
-            .tiers
-            .iter()
-            .map(|tier| {
-                let active = tier.supply - tier.slots - tier.completed;
-                active as u64 * tier.stake
-            })
-            .sum();
-        let extra_vault = self.vault.amount.saturating_sub(expected_vault_amount);
-
+        let extra_vault = self.extra(VaultKind::Stake)?;
         if extra_vault > 0 {
-            let cpi_accounts = Transfer {
-                from: self.vault.to_account_info(),
-                to: self.to.to_account_info(),
-                authority: self.pool_signer.to_account_info(),
-            };
-            token::transfer(
-                CpiContext::new_with_signer(
-                    self.token_program.to_account_info(),
-                    cpi_accounts,
-                    &[&seeds],
-                ),
-                extra_vault,
-            )?;
+            self.withdraw(VaultKind::Stake, extra_vault)?;
         }
 
         require!(
@@ -109,14 +135,18 @@ impl Withdraw<'_> {
             StakingError::OnlyExtraWithdrawAllowed
         );
 
-        Ok(())
+        Ok((extra_vault, extra_rewards))
     }
 }
 
-pub fn withdraw(pool: Pubkey, authority: Pubkey, destination: Pubkey) -> Instruction {
+pub fn withdraw(
+    pool: Pubkey,
+    pool_account: &Pool,
+    authority: Pubkey,
+    destination: Pubkey,
+    reward_destination: Pubkey,
+) -> Instruction {
     let (pool_signer, _) = Pubkey::find_program_address(&[pool.as_ref()], &ID);
-    let (vault, _) = Pubkey::find_program_address(&[b"vault".as_ref(), pool.as_ref()], &ID);
-    let (reward_vault, _) = Pubkey::find_program_address(&[b"reward".as_ref(), pool.as_ref()], &ID);
     Instruction {
         program_id: ID,
         accounts: vec![
@@ -124,10 +154,43 @@ pub fn withdraw(pool: Pubkey, authority: Pubkey, destination: Pubkey) -> Instruc
             AccountMeta::new_readonly(pool_signer, false),
             AccountMeta::new_readonly(authority, true),
             AccountMeta::new(destination, false),
-            AccountMeta::new(vault, false),
-            AccountMeta::new(reward_vault, false),
-            AccountMeta::new_readonly(token::ID, false),
+            AccountMeta::new(reward_destination, false),
+            AccountMeta::new(pool_account.vault, false),
+            AccountMeta::new(pool_account.reward_vault, false),
+            AccountMeta::new_readonly(pool_account.mint, false),
+            AccountMeta::new_readonly(pool_account.reward_mint, false),
+            AccountMeta::new_readonly(pool_account.token_program, false),
+            AccountMeta::new_readonly(pool_account.reward_token_program, false),
         ],
         data: crate::instruction::WithdrawExtra.data(),
     }
 }
+
+pub fn withdraw_amount(
+    pool: Pubkey,
+    pool_account: &Pool,
+    authority: Pubkey,
+    destination: Pubkey,
+    reward_destination: Pubkey,
+    vault: VaultKind,
+    amount: u64,
+) -> Instruction {
+    let (pool_signer, _) = Pubkey::find_program_address(&[pool.as_ref()], &ID);
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new_readonly(pool, false),
+            AccountMeta::new_readonly(pool_signer, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(destination, false),
+            AccountMeta::new(reward_destination, false),
+            AccountMeta::new(pool_account.vault, false),
+            AccountMeta::new(pool_account.reward_vault, false),
+            AccountMeta::new_readonly(pool_account.mint, false),
+            AccountMeta::new_readonly(pool_account.reward_mint, false),
+            AccountMeta::new_readonly(pool_account.token_program, false),
+            AccountMeta::new_readonly(pool_account.reward_token_program, false),
+        ],
+        data: crate::instruction::WithdrawAmount { vault, amount }.data(),
+    }
+}
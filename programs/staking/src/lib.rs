@@ -1,10 +1,11 @@
 pub mod errors;
 pub mod events;
 pub mod instructions;
+pub mod merkle;
 pub mod state;
+pub mod token_interface;
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, CloseAccount};
 
 use errors::*;
 use events::*;
@@ -17,185 +18,841 @@ declare_id!("EMVNZcM6epTkxfCcjxqv44aXNcaaPArLGAjxjMWfjsoy");
 pub mod staking {
     use super::*;
     use anchor_lang::solana_program::clock;
+    use anchor_lang::solana_program::program::set_return_data;
 
     /// Initialize new staking pool
-    pub fn initialize(ctx: Context<Initialize>, tiers: [RewardTier; 3]) -> Result<()> {
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        tiers: Vec<RewardTier>,
+        opens_at: Option<u64>,
+        closes_at: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts.check_mints()?;
+        ctx.accounts.create_vaults(&ctx.bumps)?;
+
         let authority = ctx.accounts.authority.key();
         let bumps = &ctx.bumps;
-        let vault = ctx.accounts.vault.key();
-        let reward_vault = ctx.accounts.reward_vault.key();
+        let vault = VaultKeys {
+            mint: ctx.accounts.mint.key(),
+            token_program: ctx.accounts.token_program.key(),
+            vault: ctx.accounts.vault.key(),
+        };
+        let reward_vault = VaultKeys {
+            mint: ctx.accounts.reward_mint.key(),
+            token_program: ctx.accounts.reward_token_program.key(),
+            vault: ctx.accounts.reward_vault.key(),
+        };
 
         ctx.accounts
             .pool
-            .init(authority, bumps, vault, reward_vault, tiers)
+            .init(authority, bumps, vault, reward_vault, tiers)?;
+        ctx.accounts.pool.set_staking_window(opens_at, closes_at)?;
+
+        emit!(InitializeEvent {
+            pool: ctx.accounts.pool.key(),
+            authority,
+            mint: ctx.accounts.mint.key(),
+            reward_mint: ctx.accounts.reward_mint.key(),
+            tiers: ctx.accounts.pool.tiers.clone(),
+            opens_at,
+            closes_at,
+        });
+
+        Ok(())
+    }
+
+    /// Create program-level allowlist of mints for new pools
+    pub fn create_allowlist(ctx: Context<CreateAllowlist>, mints: Vec<Pubkey>) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let bump = *ctx.bumps.get("allowlist").unwrap();
+
+        ctx.accounts.allowlist.init(authority, bump, mints)
+    }
+
+    /// Replace mints in allowlist
+    pub fn set_allowlist(ctx: Context<SetAllowlist>, mints: Vec<Pubkey>) -> Result<()> {
+        ctx.accounts.allowlist.set_mints(mints)
+    }
+
+    /// Close allowlist - any mint is allowed for new pools
+    pub fn close_allowlist(_ctx: Context<CloseAllowlist>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Fund reward vault, anyone can fund
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+        require!(amount > 0, StakingError::AmountMustBeGreaterThanZero);
+
+        let received = ctx.accounts.transfer_to_reward_vault(amount)?;
+        ctx.accounts.check_funding_cap()?;
+        ctx.accounts.pool.metrics.fund(received);
+
+        emit!(FundEvent {
+            pool: ctx.accounts.pool.key(),
+            funder: ctx.accounts.funder.key(),
+            amount: received,
+            fee: amount - received,
+        });
+
+        Ok(())
     }
 
     /// Withdraw extra
     pub fn withdraw_extra(ctx: Context<Withdraw>) -> Result<()> {
-        ctx.accounts.withdraw_extra()
+        let clock = clock::Clock::get()?;
+        require!(
+            ctx.accounts.pool.is_closed(clock.unix_timestamp as u64),
+            StakingError::PoolHasToBeClosed
+        );
+
+        let (amount, reward_amount) = ctx.accounts.withdraw_extra()?;
+
+        emit!(WithdrawExtraEvent {
+            pool: ctx.accounts.pool.key(),
+            to: ctx.accounts.to.key(),
+            reward_to: ctx.accounts.reward_to.key(),
+            amount,
+            reward_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw amount of extra from vault, extra reward can be withdrawn from open pool
+    pub fn withdraw_amount(ctx: Context<Withdraw>, vault: VaultKind, amount: u64) -> Result<()> {
+        require!(amount > 0, StakingError::AmountMustBeGreaterThanZero);
+
+        let clock = clock::Clock::get()?;
+        ctx.accounts
+            .pool
+            .check_withdraw(vault, clock.unix_timestamp as u64)?;
+        ctx.accounts.withdraw(vault, amount)?;
+
+        let to = match vault {
+            VaultKind::Stake => ctx.accounts.to.key(),
+            VaultKind::Reward => ctx.accounts.reward_to.key(),
+        };
+
+        emit!(WithdrawEvent {
+            pool: ctx.accounts.pool.key(),
+            vault,
+            amount,
+            to,
+        });
+
+        Ok(())
+    }
+
+    /// Set delay between queueing and executing withdrawals, up to MAX_WITHDRAW_DELAY.
+    /// Raising the delay is one-way, it can't be lowered or removed afterwards
+    pub fn set_withdraw_delay(ctx: Context<SetWithdrawDelay>, withdraw_delay: u64) -> Result<()> {
+        ctx.accounts.pool.set_withdraw_delay(withdraw_delay)?;
+
+        emit!(SetWithdrawDelayEvent {
+            pool: ctx.accounts.pool.key(),
+            withdraw_delay,
+        });
+
+        Ok(())
+    }
+
+    /// Queue withdrawal from vault, executable after the pool withdraw delay
+    pub fn queue_withdraw(
+        ctx: Context<QueueWithdraw>,
+        vault: VaultKind,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, StakingError::AmountMustBeGreaterThanZero);
+
+        let clock = clock::Clock::get()?;
+        let executable_at = (clock.unix_timestamp as u64)
+            .checked_add(ctx.accounts.pool.withdraw_delay)
+            .ok_or_else(|| error!(StakingError::CalcFailure))?;
+
+        let pool = ctx.accounts.pool.key();
+        let to = ctx.accounts.to.key();
+        let bump = *ctx.bumps.get("withdrawal").unwrap();
+        let index = ctx.accounts.pool.queue_withdrawal();
+
+        ctx.accounts
+            .withdrawal
+            .init(pool, index, vault, amount, to, executable_at, bump)?;
+
+        emit!(QueueWithdrawEvent {
+            pool,
+            withdrawal: ctx.accounts.withdrawal.key(),
+            vault,
+            amount,
+            to,
+            executable_at,
+        });
+
+        Ok(())
+    }
+
+    /// Execute queued withdrawal once its delay has passed
+    pub fn execute_withdraw(ctx: Context<ExecuteWithdraw>) -> Result<()> {
+        let clock = clock::Clock::get()?;
+        require!(
+            ctx.accounts.withdrawal.executable_at <= clock.unix_timestamp as u64,
+            StakingError::WithdrawalLocked
+        );
+
+        ctx.accounts.withdraw(clock.unix_timestamp as u64)?;
+
+        emit!(ExecuteWithdrawEvent {
+            pool: ctx.accounts.pool.key(),
+            withdrawal: ctx.accounts.withdrawal.key(),
+            vault: ctx.accounts.withdrawal.vault,
+            amount: ctx.accounts.withdrawal.amount,
+            to: ctx.accounts.to.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Cancel queued withdrawal
+    pub fn cancel_withdraw(ctx: Context<CancelWithdraw>) -> Result<()> {
+        emit!(CancelWithdrawEvent {
+            pool: ctx.accounts.pool.key(),
+            withdrawal: ctx.accounts.withdrawal.key(),
+        });
+
+        Ok(())
     }
 
     /// Pause - all user actions are restricted
     pub fn pause(ctx: Context<Pause>) -> Result<()> {
-        ctx.accounts.pool.pause()
+        ctx.accounts.pool.pause()?;
+
+        emit!(PauseEvent {
+            pool: ctx.accounts.pool.key(),
+            authority: ctx.accounts.authority.key(),
+        });
+
+        Ok(())
     }
 
     /// Unpause - allow user actions
     pub fn unpause(ctx: Context<Unpause>) -> Result<()> {
-        ctx.accounts.pool.unpause()
+        ctx.accounts.pool.unpause()?;
+
+        emit!(UnpauseEvent {
+            pool: ctx.accounts.pool.key(),
+            authority: ctx.accounts.authority.key(),
+        });
+
+        Ok(())
     }
 
     /// Close pool for new stakes - users can't create new stake
     pub fn close(ctx: Context<ClosePool>) -> Result<()> {
-        ctx.accounts.pool.close()
+        ctx.accounts.pool.close()?;
+
+        emit!(CloseEvent {
+            pool: ctx.accounts.pool.key(),
+            authority: ctx.accounts.authority.key(),
+        });
+
+        Ok(())
     }
 
     /// Open pool for new stakes
     pub fn open(ctx: Context<OpenPool>) -> Result<()> {
-        ctx.accounts.pool.open()
+        ctx.accounts.pool.open()?;
+
+        emit!(OpenEvent {
+            pool: ctx.accounts.pool.key(),
+            authority: ctx.accounts.authority.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Propose new pool authority, None to cancel the proposal
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        pending_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.pool.propose_authority(pending_authority)?;
+
+        emit!(ProposeAuthorityEvent {
+            pool: ctx.accounts.pool.key(),
+            authority: ctx.accounts.authority.key(),
+            pending_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Accept pool authority by the proposed authority
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let previous_authority = ctx.accounts.pool.authority;
+        ctx.accounts.pool.accept_authority()?;
+
+        emit!(AcceptAuthorityEvent {
+            pool: ctx.accounts.pool.key(),
+            previous_authority,
+            authority: ctx.accounts.pool.authority,
+        });
+
+        Ok(())
+    }
+
+    /// Assign role to key, None to return the role to the authority
+    pub fn set_role(ctx: Context<SetRole>, role: Role, key: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.pool.set_role(role, key)?;
+
+        emit!(SetRoleEvent {
+            pool: ctx.accounts.pool.key(),
+            role,
+            key,
+        });
+
+        Ok(())
+    }
+
+    /// Set timestamps the pool opens and closes for new stakes, None for no limit
+    pub fn set_staking_window(
+        ctx: Context<SetStakingWindow>,
+        opens_at: Option<u64>,
+        closes_at: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts.pool.set_staking_window(opens_at, closes_at)?;
+
+        emit!(SetStakingWindowEvent {
+            pool: ctx.accounts.pool.key(),
+            opens_at,
+            closes_at,
+        });
+
+        Ok(())
+    }
+
+    /// Set root of merkle tree of wallets allowed to create a user account, None to allow anyone.
+    /// The allowlist gates new user creation only: rotating the root doesn't revoke existing users,
+    /// they keep staking with the allowance proven on creation
+    pub fn set_merkle_root(
+        ctx: Context<SetMerkleRoot>,
+        merkle_root: Option<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.pool.set_merkle_root(merkle_root)?;
+
+        emit!(SetMerkleRootEvent {
+            pool: ctx.accounts.pool.key(),
+            merkle_root,
+        });
+
+        Ok(())
+    }
+
+    /// Enable or disable solvency mode - stake fails if reward vault can't cover the reward
+    pub fn set_solvency_check(ctx: Context<SetSolvencyCheck>, enabled: bool) -> Result<()> {
+        ctx.accounts.pool.set_solvency_check(enabled)?;
+
+        emit!(SetSolvencyCheckEvent {
+            pool: ctx.accounts.pool.key(),
+            enabled,
+        });
+
+        Ok(())
+    }
+
+    /// Enable or disable funding cap - funding above the total payable reward fails
+    pub fn set_funding_cap(ctx: Context<SetFundingCap>, enabled: bool) -> Result<()> {
+        ctx.accounts.pool.set_funding_cap(enabled)?;
+
+        emit!(SetFundingCapEvent {
+            pool: ctx.accounts.pool.key(),
+            enabled,
+        });
+
+        Ok(())
+    }
+
+    /// Update settings or availability of reward tier
+    pub fn update_tier(ctx: Context<UpdateTier>, tier: u8, update: TierUpdate) -> Result<()> {
+        ctx.accounts.pool.tiers[tier as usize].update(&update)?;
+        ctx.accounts.pool.check_penalties()?;
+
+        emit!(UpdateTierEvent {
+            pool: ctx.accounts.pool.key(),
+            tier,
+            update,
+        });
+
+        Ok(())
+    }
+
+    /// Set token account for early unstake penalties
+    pub fn set_treasury(ctx: Context<SetTreasury>) -> Result<()> {
+        let treasury = ctx.accounts.treasury.key();
+        ctx.accounts.pool.set_treasury(treasury)?;
+
+        emit!(SetTreasuryEvent {
+            pool: ctx.accounts.pool.key(),
+            treasury,
+        });
+
+        Ok(())
+    }
+
+    /// Enable emergency mode - users can withdraw principal even if paused
+    pub fn enable_emergency(ctx: Context<EnableEmergency>) -> Result<()> {
+        ctx.accounts.pool.enable_emergency()?;
+
+        emit!(EnableEmergencyEvent {
+            pool: ctx.accounts.pool.key(),
+            authority: ctx.accounts.authority.key(),
+        });
+
+        Ok(())
     }
 
     /// Initialize a user staking account
     pub fn create_user(ctx: Context<CreateUser>) -> Result<()> {
+        let clock = clock::Clock::get()?;
+        ctx.accounts.pool.check_open(clock.unix_timestamp as u64)?;
+        require!(
+            ctx.accounts.pool.merkle_root.is_none(),
+            StakingError::ProofRequired
+        );
+
         let pool = ctx.accounts.pool.key();
         let authority = ctx.accounts.authority.key();
         let bump = *ctx.bumps.get("user").unwrap();
+        let tiers = ctx.accounts.pool.tiers.len();
+
+        ctx.accounts.user.init(pool, authority, bump, tiers)?;
+
+        emit!(CreateUserEvent {
+            pool,
+            user: ctx.accounts.user.key(),
+            authority,
+            allowance: 0,
+        });
+
+        Ok(())
+    }
+
+    /// Initialize a user staking account with proof the wallet is in the pool allowlist,
+    /// the proof is checked only here and not again on stake
+    pub fn create_user_with_proof(
+        ctx: Context<CreateUser>,
+        proof: Vec<[u8; 32]>,
+        allowance: u16,
+    ) -> Result<()> {
+        let clock = clock::Clock::get()?;
+        ctx.accounts.pool.check_open(clock.unix_timestamp as u64)?;
+
+        let pool = ctx.accounts.pool.key();
+        let authority = ctx.accounts.authority.key();
+        let bump = *ctx.bumps.get("user").unwrap();
+        let tiers = ctx.accounts.pool.tiers.len();
+
+        let allowance = match ctx.accounts.pool.merkle_root {
+            Some(root) => {
+                let leaf = merkle::leaf(&authority, allowance);
+                require!(
+                    merkle::verify(&proof, &root, leaf),
+                    StakingError::InvalidProof
+                );
+                allowance
+            }
+            None => 0,
+        };
+
+        ctx.accounts.user.init(pool, authority, bump, tiers)?;
+        ctx.accounts.user.allowance = allowance;
+
+        emit!(CreateUserEvent {
+            pool,
+            user: ctx.accounts.user.key(),
+            authority,
+            allowance,
+        });
+
+        Ok(())
+    }
+
+    /// Set claim delegate and reward destination of the user, None to unset,
+    /// the delegate can claim only if the reward destination is set
+    pub fn set_delegate(
+        ctx: Context<SetDelegate>,
+        delegate: Option<Pubkey>,
+        reward_destination: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.user.delegate = delegate;
+        ctx.accounts.user.reward_destination = reward_destination;
+
+        emit!(SetDelegateEvent {
+            pool: ctx.accounts.pool.key(),
+            user: ctx.accounts.user.key(),
+            delegate,
+            reward_destination,
+        });
 
-        ctx.accounts.user.init(pool, authority, bump)
+        Ok(())
     }
 
     /// Stake tokens
-    pub fn stake(ctx: Context<Stake>, tier: Tier) -> Result<()> {
-        let reward_tier: &RewardTier = &ctx.accounts.pool.tiers[tier as usize];
-        let user_stake: &StakeStatus = &ctx.accounts.user.stakes[tier as usize];
+    pub fn stake(ctx: Context<Stake>, tier: u8, stake: u64) -> Result<()> {
+        let clock = clock::Clock::get()?;
+        ctx.accounts.pool.check_open(clock.unix_timestamp as u64)?;
+
+        let reward_tier: RewardTier = ctx.accounts.pool.tiers[tier as usize];
 
         require!(reward_tier.slots > 0, StakingError::NoAvailableSlotForTier);
-        require!(user_stake.is_none(), StakingError::TierAlreadyUsed);
+        require!(
+            reward_tier.allows_stake(stake),
+            StakingError::InvalidStakeAmount
+        );
 
         let locked_until = reward_tier.locked_until()?;
-        let amount = reward_tier.stake;
-        let reward = reward_tier.reward;
 
-        token::transfer(ctx.accounts.into(), amount)?;
+        let index = ctx
+            .accounts
+            .user
+            .open_position(tier, reward_tier.max_per_wallet)?;
+        ctx.accounts.check_solvency(&reward_tier, stake)?;
+        let amount = ctx.accounts.transfer_to_vault(stake)?;
+        let reward = reward_tier.reward(amount)?;
+        require!(reward > 0, StakingError::AmountMustBeGreaterThanZero);
+
+        let pool = ctx.accounts.pool.key();
+        let user = ctx.accounts.user.key();
+        let bump = *ctx.bumps.get("position").unwrap();
+        let receipt_bump = *ctx.bumps.get("receipt_mint").unwrap();
+        let status = StakeStatus::new_stake(locked_until, amount, reward)?;
 
         ctx.accounts.pool.tiers[tier as usize].use_slot();
-        ctx.accounts.user.stakes[tier as usize] = StakeStatus::new_stake(locked_until)?;
-        ctx.accounts.pool.metrics.stake(reward);
+        ctx.accounts
+            .position
+            .init(pool, user, index, tier, status, bump, receipt_bump)?;
+        ctx.accounts.pool.metrics.stake(amount, reward);
+        ctx.accounts.create_receipt(receipt_bump)?;
 
         emit!(StakeEvent {
-            pool: ctx.accounts.pool.key(),
-            user: ctx.accounts.user.key(),
+            pool,
+            user,
+            position: ctx.accounts.position.key(),
+            receipt: ctx.accounts.receipt_mint.key(),
             tier,
             locked_until,
-            amount
+            amount,
+            fee: stake - amount,
+            reward,
         });
 
         Ok(())
     }
 
     /// Unstake tokens
-    pub fn unstake(ctx: Context<Unstake>, tier: Tier) -> Result<()> {
-        let stake: &StakeStatus = &ctx.accounts.user.stakes[tier as usize];
+    pub fn unstake(ctx: Context<Unstake>) -> Result<()> {
+        let tier = ctx.accounts.position.tier;
 
         let clock = clock::Clock::get()?;
-        match stake {
-            StakeStatus::None | StakeStatus::Used => return err!(StakingError::UserDoesntHaveTier),
+        let amount = match ctx.accounts.position.status {
             StakeStatus::Staking { locked_until, .. } => {
-                return if *locked_until > clock.unix_timestamp as u64 {
+                return if locked_until > clock.unix_timestamp as u64 {
                     err!(StakingError::TimeLockHasntYetPassed)
                 } else {
                     err!(StakingError::PendingReward)
                 }
             }
-            _ => {}
-        }
-
-        let reward_tier: &RewardTier = &ctx.accounts.pool.tiers[tier as usize];
-        let amount = reward_tier.stake;
-
-        let key = ctx.accounts.user.pool;
-        let seeds = [key.as_ref(), &[ctx.accounts.pool.bump]];
+            StakeStatus::Ready { amount } => amount,
+        };
 
-        let cpi_ctx: CpiContext<_> = ctx.accounts.into();
-        token::transfer(cpi_ctx.with_signer(&[&seeds]), amount)?;
+        let fee = ctx.accounts.transfer_from_vault(amount)?;
 
-        ctx.accounts.user.stakes[tier as usize] = StakeStatus::Used;
+        ctx.accounts.burn_receipt()?;
+        ctx.accounts.user.close_position(tier);
         ctx.accounts.pool.tiers[tier as usize].complete();
+        ctx.accounts.pool.metrics.unstake(amount);
 
         emit!(UnstakeEvent {
             pool: ctx.accounts.pool.key(),
             user: ctx.accounts.user.key(),
+            position: ctx.accounts.position.key(),
             tier,
             amount,
+            fee,
         });
 
         Ok(())
     }
 
-    /// Claim reward
-    pub fn claim(ctx: Context<Claim>) -> Result<()> {
-        require!(
-            ctx.accounts
-                .user
-                .stakes
-                .iter()
-                .any(|stake| matches!(stake, StakeStatus::Staking { .. })),
-            StakingError::UserDoensntHaveStakes
-        );
+    /// Unstake tokens before the time lock, paying tier penalty
+    pub fn early_unstake(ctx: Context<EarlyUnstake>) -> Result<()> {
+        let tier = ctx.accounts.position.tier;
+        let reward_tier: RewardTier = ctx.accounts.pool.tiers[tier as usize];
+        let stake: StakeStatus = ctx.accounts.position.status;
 
-        let clock = anchor_lang::solana_program::clock::Clock::get()?;
+        let clock = clock::Clock::get()?;
         let now = clock.unix_timestamp as u64;
+        let (amount, total_reward, reward_paid) = match stake {
+            StakeStatus::Staking {
+                locked_until,
+                reward_paid,
+                amount,
+                reward,
+                ..
+            } if locked_until > now => (amount, reward, reward_paid),
+            StakeStatus::Staking { .. } | StakeStatus::Ready { .. } => {
+                return err!(StakingError::TimeLockHasPassed)
+            }
+        };
+
+        let reward = if reward_tier.forfeit_reward {
+            0
+        } else {
+            stake.update_reword(&reward_tier, now).0
+        };
+        let forfeited = total_reward - reward_paid - reward;
+        let penalty = reward_tier.early_unstake_penalty(amount);
 
-        let new_state: Vec<(u64, StakeStatus)> = ctx
+        let fee = ctx
             .accounts
-            .user
-            .stakes
-            .iter()
-            .zip(ctx.accounts.pool.tiers.iter())
-            .map(|(stake, tier)| stake.update_reword(tier, now))
-            .collect();
+            .transfer_from_vault(amount - penalty, penalty)?;
+        if reward > 0 {
+            ctx.accounts.transfer_reward(reward)?;
+        }
 
-        let amount: u64 = new_state.iter().map(|(value, _)| value).sum();
+        ctx.accounts.burn_receipt()?;
+        ctx.accounts.user.close_position(tier);
+        ctx.accounts.pool.tiers[tier as usize].complete();
+        ctx.accounts.pool.metrics.unstake(amount);
+        ctx.accounts.pool.metrics.claim(reward);
+        ctx.accounts.pool.metrics.forfeit(forfeited);
 
-        require!(amount > 0, StakingError::AmountMustBeGreaterThanZero);
+        emit!(EarlyUnstakeEvent {
+            pool: ctx.accounts.pool.key(),
+            user: ctx.accounts.user.key(),
+            position: ctx.accounts.position.key(),
+            tier,
+            amount: amount - penalty,
+            fee,
+            penalty,
+            reward,
+            forfeited,
+        });
 
-        let key = ctx.accounts.user.pool;
-        let seeds = [key.as_ref(), &[ctx.accounts.pool.bump]];
+        Ok(())
+    }
 
-        let cpi_ctx: CpiContext<_> = ctx.accounts.into();
-        token::transfer(cpi_ctx.with_signer(&[&seeds]), amount)?;
+    /// Claim remaining reward and unstake tokens after the time lock
+    pub fn exit(ctx: Context<Exit>) -> Result<()> {
+        let tier = ctx.accounts.position.tier;
+        let reward_tier: RewardTier = ctx.accounts.pool.tiers[tier as usize];
 
-        for (stake, new_stake) in ctx
+        let clock = clock::Clock::get()?;
+        let now = clock.unix_timestamp as u64;
+        let (reward, status) = ctx
             .accounts
-            .user
-            .stakes
-            .iter_mut()
-            .zip(new_state.into_iter().map(|(_, stake)| stake))
-        {
-            *stake = new_stake;
+            .position
+            .status
+            .update_reword(&reward_tier, now);
+        let amount = match status {
+            StakeStatus::Staking { .. } => return err!(StakingError::TimeLockHasntYetPassed),
+            StakeStatus::Ready { amount } => amount,
+        };
+
+        let reward_fee = if reward > 0 {
+            ctx.accounts.transfer_reward(reward)?
+        } else {
+            0
+        };
+        let fee = ctx.accounts.transfer_from_vault(amount)?;
+
+        ctx.accounts.burn_receipt()?;
+        ctx.accounts.user.close_position(tier);
+        ctx.accounts.pool.tiers[tier as usize].complete();
+        ctx.accounts.pool.metrics.unstake(amount);
+        ctx.accounts.pool.metrics.claim(reward);
+
+        emit!(ExitEvent {
+            pool: ctx.accounts.pool.key(),
+            user: ctx.accounts.user.key(),
+            position: ctx.accounts.position.key(),
+            tier,
+            amount,
+            fee,
+            reward,
+            reward_fee,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw principal in emergency mode, unpaid reward is forfeited
+    pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+        let tier = ctx.accounts.position.tier;
+
+        let (amount, forfeited) = match ctx.accounts.position.status {
+            StakeStatus::Staking {
+                reward_paid,
+                amount,
+                reward,
+                ..
+            } => (amount, reward - reward_paid),
+            StakeStatus::Ready { amount } => (amount, 0),
+        };
+
+        let fee = ctx.accounts.transfer_from_vault(amount)?;
+
+        ctx.accounts.burn_receipt()?;
+        ctx.accounts.user.close_position(tier);
+        ctx.accounts.pool.tiers[tier as usize].complete();
+        ctx.accounts.pool.metrics.unstake(amount);
+        ctx.accounts.pool.metrics.forfeit(forfeited);
+
+        emit!(EmergencyWithdrawEvent {
+            pool: ctx.accounts.pool.key(),
+            user: ctx.accounts.user.key(),
+            position: ctx.accounts.position.key(),
+            tier,
+            amount,
+            fee,
+            forfeited,
+        });
+
+        Ok(())
+    }
+
+    /// Move staking or ready position to another wallet, recipient user account is created if needed
+    pub fn transfer_position(ctx: Context<TransferPosition>) -> Result<()> {
+        let pool = ctx.accounts.pool.key();
+        let recipient = ctx.accounts.recipient.key();
+        let tier = ctx.accounts.position.tier;
+        let status = ctx.accounts.position.status;
+
+        if ctx.accounts.recipient_user.pool == Pubkey::default() {
+            require!(
+                ctx.accounts.pool.merkle_root.is_none(),
+                StakingError::ProofRequired
+            );
+            let bump = *ctx.bumps.get("recipient_user").unwrap();
+            let tiers = ctx.accounts.pool.tiers.len();
+            ctx.accounts
+                .recipient_user
+                .init(pool, recipient, bump, tiers)?;
         }
 
-        ctx.accounts.pool.metrics.claim(amount);
+        let max_per_wallet = ctx.accounts.pool.tiers[tier as usize].max_per_wallet;
+        let index = ctx
+            .accounts
+            .recipient_user
+            .open_position(tier, max_per_wallet)?;
+        ctx.accounts.user.close_position(tier);
+
+        let recipient_user = ctx.accounts.recipient_user.key();
+        let bump = *ctx.bumps.get("recipient_position").unwrap();
+        let receipt_bump = *ctx.bumps.get("recipient_receipt_mint").unwrap();
+        ctx.accounts.recipient_position.init(
+            pool,
+            recipient_user,
+            index,
+            tier,
+            status,
+            bump,
+            receipt_bump,
+        )?;
+
+        ctx.accounts.burn_receipt()?;
+        ctx.accounts.create_receipt(receipt_bump)?;
+
+        emit!(TransferPositionEvent {
+            pool,
+            from: ctx.accounts.authority.key(),
+            to: recipient,
+            from_position: ctx.accounts.position.key(),
+            to_position: ctx.accounts.recipient_position.key(),
+            tier,
+        });
+
+        Ok(())
+    }
+
+    /// Claim reward of user positions passed as remaining accounts
+    pub fn claim<'info>(ctx: Context<'_, '_, '_, 'info, Claim<'info>>) -> Result<()> {
+        let mut positions = load_positions(ctx.remaining_accounts, &ctx.accounts.user.key())?;
+
+        let (amount, fee) = ctx.accounts.claim_positions(&mut positions)?;
 
         emit!(ClaimEvent {
             pool: ctx.accounts.pool.key(),
             user: ctx.accounts.user.key(),
             amount,
+            fee,
+        });
+
+        Ok(())
+    }
+
+    /// Claim reward of the positions in a single tier, other positions are skipped
+    pub fn claim_tier<'info>(
+        ctx: Context<'_, '_, '_, 'info, Claim<'info>>,
+        tier: u8,
+    ) -> Result<()> {
+        require!(
+            (tier as usize) < ctx.accounts.pool.tiers.len(),
+            StakingError::InvalidTier
+        );
+
+        let mut positions = load_positions(ctx.remaining_accounts, &ctx.accounts.user.key())?;
+        positions.retain(|(position, _)| position.tier == tier);
+
+        let (amount, fee) = ctx.accounts.claim_positions(&mut positions)?;
+
+        emit!(ClaimTierEvent {
+            pool: ctx.accounts.pool.key(),
+            user: ctx.accounts.user.key(),
+            tier,
+            amount,
+            fee,
         });
 
         Ok(())
     }
 
+    /// View claimable reward of user positions passed as remaining accounts,
+    /// result is set as return data of the transaction
+    pub fn pending_rewards<'info>(
+        ctx: Context<'_, '_, '_, 'info, PendingRewards<'info>>,
+    ) -> Result<()> {
+        let clock = clock::Clock::get()?;
+        let rewards = ctx
+            .accounts
+            .rewards(ctx.remaining_accounts, clock.unix_timestamp as u64)?;
+
+        set_return_data(&rewards.try_to_vec()?);
+
+        Ok(())
+    }
+
+    /// View state of pool and its vaults, result is set as return data of the transaction
+    pub fn pool_summary(ctx: Context<PoolSummary>) -> Result<()> {
+        let clock = clock::Clock::get()?;
+        let stats = ctx.accounts.stats(clock.unix_timestamp as u64)?;
+
+        set_return_data(&stats.try_to_vec()?);
+
+        Ok(())
+    }
+
     pub fn free_user(ctx: Context<FreeUser>) -> Result<()> {
         require!(
-            ctx.accounts
-                .user
-                .stakes
-                .iter()
-                .all(|stake| matches!(stake, StakeStatus::Used)
-                    || matches!(stake, StakeStatus::None)),
+            !ctx.accounts.user.has_positions(),
             StakingError::UserHasActiveStakes
         );
+
+        emit!(FreeUserEvent {
+            pool: ctx.accounts.pool.key(),
+            user: ctx.accounts.user.key(),
+            authority: ctx.accounts.user.authority,
+            receiver: ctx.accounts.receiver.key(),
+        });
+
         Ok(())
     }
 
@@ -209,40 +866,20 @@ pub mod staking {
             StakingError::UserHasActiveStakes
         );
         require!(
-            ctx.accounts.vault.amount == 0,
+            token_interface::amount(&ctx.accounts.vault)? == 0,
             StakingError::AmountMustBeZero
         );
         require!(
-            ctx.accounts.reward_vault.amount == 0,
+            token_interface::amount(&ctx.accounts.reward_vault)? == 0,
             StakingError::AmountMustBeZero
         );
 
-        let key = ctx.accounts.pool.key();
-        let seeds = [key.as_ref(), &[ctx.accounts.pool.bump]];
+        ctx.accounts.close_vaults()?;
 
-        // Close vault
-        let cpi_accounts = CloseAccount {
-            account: ctx.accounts.vault.to_account_info(),
-            destination: ctx.accounts.receiver.to_account_info(),
-            authority: ctx.accounts.pool_signer.to_account_info(),
-        };
-        token::close_account(CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            &[&seeds],
-        ))?;
-
-        // Close reward_vault
-        let cpi_accounts = CloseAccount {
-            account: ctx.accounts.reward_vault.to_account_info(),
-            destination: ctx.accounts.receiver.to_account_info(),
-            authority: ctx.accounts.pool_signer.to_account_info(),
-        };
-        token::close_account(CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            &[&seeds],
-        ))?;
+        emit!(FreePoolEvent {
+            pool: ctx.accounts.pool.key(),
+            receiver: ctx.accounts.receiver.key(),
+        });
 
         Ok(())
     }
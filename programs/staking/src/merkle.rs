@@ -0,0 +1,25 @@
+//! Merkle tree of wallets allowed to stake, leaves are hashes of wallet and tier allowance
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Leaf of wallet allowed to open `allowance` positions per tier, 0 for no limit
+pub fn leaf(wallet: &Pubkey, allowance: u16) -> [u8; 32] {
+    hashv(&[wallet.as_ref(), &allowance.to_le_bytes()]).to_bytes()
+}
+
+/// Hash of sorted pair, so the proof doesn't need positions of the nodes
+pub fn parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[a, b]).to_bytes()
+    } else {
+        hashv(&[b, a]).to_bytes()
+    }
+}
+
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    proof
+        .iter()
+        .fold(leaf, |node, sibling| parent(&node, sibling))
+        == *root
+}
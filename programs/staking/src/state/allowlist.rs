@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+use std::mem::size_of;
+
+/// Max number of mints in allowlist
+pub const MAX_ALLOWED_MINTS: usize = 16;
+
+/// Program-level list of mints allowed for new pools
+#[account]
+pub struct Allowlist {
+    /// Privileged account.
+    pub authority: Pubkey,
+    /// Mints allowed for new pools
+    pub mints: Vec<Pubkey>,
+    /// Signer bump
+    pub bump: u8,
+}
+
+impl Allowlist {
+    pub fn space() -> usize {
+        8 + size_of::<Allowlist>() + MAX_ALLOWED_MINTS * size_of::<Pubkey>()
+    }
+
+    pub fn allows(&self, mint: &Pubkey) -> bool {
+        self.mints.contains(mint)
+    }
+}
+
+pub trait AllowlistAccount {
+    fn init(&mut self, authority: Pubkey, bump: u8, mints: Vec<Pubkey>) -> Result<()>;
+    fn set_mints(&mut self, mints: Vec<Pubkey>) -> Result<()>;
+}
+
+impl AllowlistAccount for Account<'_, Allowlist> {
+    fn init(&mut self, authority: Pubkey, bump: u8, mints: Vec<Pubkey>) -> Result<()> {
+        self.authority = authority;
+        self.bump = bump;
+        self.set_mints(mints)
+    }
+
+    fn set_mints(&mut self, mints: Vec<Pubkey>) -> Result<()> {
+        require!(
+            mints.len() <= MAX_ALLOWED_MINTS,
+            StakingError::TooManyAllowedMints
+        );
+        self.mints = mints;
+        Ok(())
+    }
+}
@@ -1,5 +1,11 @@
+pub mod allowlist;
 pub mod pool;
+pub mod position;
 pub mod user;
+pub mod withdrawal;
 
+pub use allowlist::*;
 pub use pool::*;
+pub use position::*;
 pub use user::*;
+pub use withdrawal::*;
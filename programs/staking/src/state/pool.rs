@@ -3,41 +3,169 @@ use anchor_lang::solana_program::clock;
 use std::collections::BTreeMap;
 
 use crate::errors::*;
+use crate::VaultKind;
 use std::mem::size_of;
 
+/// Max number of reward tiers in a pool
+pub const MAX_TIERS: usize = 8;
+
+/// Scale of tier reward rate, rate of REWARD_RATE_SCALE is 100% reward for duration
+pub const REWARD_RATE_SCALE: u64 = 1_000_000_000;
+
+/// Max delay of queued withdrawals, one year
+pub const MAX_WITHDRAW_DELAY: u64 = 365 * 24 * 60 * 60;
+
 #[account]
 pub struct Pool {
     /// Privileged account.
     pub authority: Pubkey,
+    /// Proposed new authority, becomes authority once accepted
+    pub pending_authority: Option<Pubkey>,
+    /// Optional key allowed to pause instead of the authority
+    pub pauser: Option<Pubkey>,
+    /// Optional key allowed to unpause, open and close instead of the authority
+    pub operator: Option<Pubkey>,
+    /// Optional key allowed to withdraw extra and free accounts instead of the authority
+    pub treasurer: Option<Pubkey>,
     /// Bump to derive the PDA owning the vaults.
     pub bump: u8,
     /// Paused state of the program - all user action restricted
     pub paused: bool,
     /// Closed for new stakes - new stakes are restricted
     pub closed: bool,
+    /// Start of the staking window, new stakes are restricted before
+    pub opens_at: Option<u64>,
+    /// End of the staking window, new stakes are restricted after
+    pub closes_at: Option<u64>,
+    /// Root of merkle tree of wallets allowed to stake, anyone can stake if not set
+    pub merkle_root: Option<[u8; 32]>,
+    /// Solvency mode - new stakes require reward vault covering all unpaid rewards
+    pub solvency_check: bool,
+    /// Funding cap - reward funding above the total payable reward is refused
+    pub funding_cap: bool,
+    /// Emergency mode - users can withdraw principal, unpaid reward is forfeited
+    pub emergency: bool,
+    /// Mint of staked tokens
+    pub mint: Pubkey,
+    /// Token program of staked mint
+    pub token_program: Pubkey,
     /// The vault holding users' tokens
     pub vault: Pubkey,
     pub vault_bump: u8,
+    /// Mint of reward tokens
+    pub reward_mint: Pubkey,
+    /// Token program of reward mint
+    pub reward_token_program: Pubkey,
     /// The vault to store reward tokens
     pub reward_vault: Pubkey,
     pub reward_vault_bump: u8,
+    /// Token account for early unstake penalties, reward vault if not set
+    pub treasury: Pubkey,
+    /// Delay between queueing and executing a withdrawal, seconds
+    pub withdraw_delay: u64,
+    /// Index of the next queued withdrawal, part of withdrawal PDA seeds
+    pub next_withdrawal: u64,
     /// Reward tiers
-    pub tiers: [RewardTier; 3],
+    pub tiers: Vec<RewardTier>,
     /// Metrics
     pub metrics: Metrics,
 }
 
 impl Pool {
-    pub fn space() -> usize {
-        size_of::<Pool>()
+    pub fn space(tiers: usize) -> usize {
+        8 + size_of::<Pool>() + tiers * size_of::<RewardTier>()
     }
-}
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
-pub enum Tier {
-    Tier500 = 0,
-    Tier1000 = 1,
-    Tier1500 = 2,
+    /// Unpaid reward of open positions and max reward of all available slots
+    pub fn max_payable(&self) -> Result<u64> {
+        self.tiers
+            .iter()
+            .try_fold(self.metrics.unpaid_reward(), |total, tier| {
+                tier.reward(tier.max_stake)?
+                    .checked_mul(tier.slots as u64)
+                    .and_then(|reward| total.checked_add(reward))
+                    .ok_or_else(|| error!(StakingError::CalcFailure))
+            })
+    }
+
+    /// Mint of tokens held by vault
+    pub fn vault_mint(&self, vault: VaultKind) -> Pubkey {
+        match vault {
+            VaultKind::Stake => self.mint,
+            VaultKind::Reward => self.reward_mint,
+        }
+    }
+
+    /// Amount of vault required for stakers, only extra above it can be withdrawn
+    pub fn vault_required(&self, vault: VaultKind) -> u64 {
+        match vault {
+            VaultKind::Stake => self.metrics.staked,
+            VaultKind::Reward => self.metrics.unpaid_reward(),
+        }
+    }
+
+    /// Extra staked tokens can be withdrawn from closed pool only, extra reward anytime
+    pub fn check_withdraw(&self, vault: VaultKind, now: u64) -> Result<()> {
+        require!(
+            vault == VaultKind::Reward || self.is_closed(now),
+            StakingError::PoolHasToBeClosed
+        );
+        Ok(())
+    }
+
+    /// Queue withdrawal, returns index of the new withdrawal
+    pub fn queue_withdrawal(&mut self) -> u64 {
+        let index = self.next_withdrawal;
+        self.next_withdrawal += 1;
+        index
+    }
+
+    /// Key acting in role, the authority if the role is not assigned
+    pub fn role_key(&self, role: Role) -> Pubkey {
+        match role {
+            Role::Pauser => self.pauser,
+            Role::Operator => self.operator,
+            Role::Treasurer => self.treasurer,
+        }
+        .unwrap_or(self.authority)
+    }
+
+    /// Closed by authority, after the staking window or once all tier slots are used
+    pub fn is_closed(&self, now: u64) -> bool {
+        self.closed
+            || matches!(self.closes_at, Some(closes_at) if now >= closes_at)
+            || self.tiers.iter().all(|tier| tier.slots == 0)
+    }
+
+    /// Check pool accepts new stakes at the moment
+    pub fn check_open(&self, now: u64) -> Result<()> {
+        require!(!self.is_closed(now), StakingError::PoolClosed);
+        require!(
+            !matches!(self.opens_at, Some(opens_at) if now < opens_at),
+            StakingError::PoolNotOpenYet
+        );
+        Ok(())
+    }
+
+    /// Destination of early unstake penalties
+    pub fn penalty_destination(&self) -> Pubkey {
+        if self.treasury == Pubkey::default() {
+            self.reward_vault
+        } else {
+            self.treasury
+        }
+    }
+
+    /// Penalties can go to reward vault only if it holds the staked mint
+    pub fn check_penalties(&self) -> Result<()> {
+        require!(
+            self.treasury != Pubkey::default()
+                || self.reward_mint == self.mint
+                || self.tiers.iter().all(|tier| tier.penalty == 0),
+            StakingError::TreasuryRequired
+        );
+        Ok(())
+    }
 }
 
 /// Settings and state of reward for tier
@@ -49,12 +177,22 @@ pub struct RewardTier {
     pub slots: u16,
     /// Completed and unstaked slots
     pub completed: u16,
-    /// Stake size
-    pub stake: u64,
+    /// Min stake size
+    pub min_stake: u64,
+    /// Max stake size
+    pub max_stake: u64,
     /// Lock duration
     pub duration: u64,
-    /// Total reward for duration
-    pub reward: u64,
+    /// Total reward for duration per staked token, scaled by REWARD_RATE_SCALE
+    pub reward_rate: u64,
+    /// Vesting of reward over duration
+    pub curve: RewardCurve,
+    /// Early unstake penalty, percent of stake
+    pub penalty: u8,
+    /// Unclaimed reward is forfeited on early unstake
+    pub forfeit_reward: bool,
+    /// Max open positions per wallet, 0 - unlimited
+    pub max_per_wallet: u16,
 }
 
 impl RewardTier {
@@ -62,9 +200,64 @@ impl RewardTier {
         self.supply > 0
             && self.slots == self.supply
             && self.completed == 0
-            && self.stake > 0
+            && self.min_stake > 0
+            && self.min_stake <= self.max_stake
             && self.duration > 0
-            && self.reward > 0
+            && self.reward_rate > 0
+            && self.curve.check(self.duration)
+            && self.penalty <= 100
+    }
+
+    fn has_stakers(&self) -> bool {
+        self.slots != self.supply || self.completed != 0
+    }
+
+    pub fn update(&mut self, update: &TierUpdate) -> Result<()> {
+        match *update {
+            TierUpdate::Config {
+                supply,
+                min_stake,
+                max_stake,
+                duration,
+                reward_rate,
+                curve,
+                penalty,
+                forfeit_reward,
+                max_per_wallet,
+            } => {
+                require!(!self.has_stakers(), StakingError::TierHasStakers);
+                let tier = RewardTier {
+                    supply,
+                    slots: supply,
+                    completed: 0,
+                    min_stake,
+                    max_stake,
+                    duration,
+                    reward_rate,
+                    curve,
+                    penalty,
+                    forfeit_reward,
+                    max_per_wallet,
+                };
+                require!(tier.check(), StakingError::InvalidRewardTier);
+                *self = tier;
+            }
+            TierUpdate::AddSlots { slots } => {
+                require!(slots > 0, StakingError::AmountMustBeGreaterThanZero);
+                self.supply = self
+                    .supply
+                    .checked_add(slots)
+                    .ok_or_else(|| error!(StakingError::CalcFailure))?;
+                self.slots += slots;
+            }
+            TierUpdate::Retire => {
+                require!(self.slots > 0, StakingError::NoAvailableSlotForTier);
+                // Keep supply - slots (taken slots) as is
+                self.supply -= self.slots;
+                self.slots = 0;
+            }
+        }
+        Ok(())
     }
 
     pub fn use_slot(&mut self) {
@@ -75,6 +268,21 @@ impl RewardTier {
         self.completed += 1;
     }
 
+    pub fn allows_stake(&self, amount: u64) -> bool {
+        amount >= self.min_stake && amount <= self.max_stake
+    }
+
+    /// Total reward for staked amount
+    pub fn reward(&self, amount: u64) -> Result<u64> {
+        u64::try_from(amount as u128 * self.reward_rate as u128 / REWARD_RATE_SCALE as u128)
+            .map_err(|_| error!(StakingError::CalcFailure))
+    }
+
+    /// Penalty for early unstake of amount
+    pub fn early_unstake_penalty(&self, amount: u64) -> u64 {
+        ((amount as u128 * self.penalty as u128) / 100) as u64
+    }
+
     pub fn locked_until(&self) -> Result<u64> {
         let clock = clock::Clock::get()?;
         (clock.unix_timestamp as u64)
@@ -83,22 +291,136 @@ impl RewardTier {
     }
 }
 
+/// Vesting curve of tier reward
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default)]
+pub enum RewardCurve {
+    /// Reward accrues evenly over duration
+    #[default]
+    Linear,
+    /// Nothing vested until cliff, linear afterwards
+    Cliff { cliff: u64 },
+    /// Reward unlocks in equal parts at the end of each step
+    Stepped { step: u64 },
+    /// Reward accrues quadratically, most of it at the end of duration
+    BackLoaded,
+}
+
+impl RewardCurve {
+    fn check(&self, duration: u64) -> bool {
+        match *self {
+            RewardCurve::Linear | RewardCurve::BackLoaded => true,
+            RewardCurve::Cliff { cliff } => cliff <= duration,
+            RewardCurve::Stepped { step } => step > 0 && step <= duration,
+        }
+    }
+
+    /// Reward vested after elapsed time of duration
+    pub fn vested(&self, reward: u64, elapsed: u64, duration: u64) -> u64 {
+        if elapsed >= duration {
+            return reward;
+        }
+
+        let reward = reward as u128;
+        let elapsed = elapsed as u128;
+        let duration = duration as u128;
+        let vested = match *self {
+            RewardCurve::Linear => reward * elapsed / duration,
+            RewardCurve::Cliff { cliff } => {
+                if elapsed < cliff as u128 {
+                    0
+                } else {
+                    reward * elapsed / duration
+                }
+            }
+            RewardCurve::Stepped { step } => {
+                let step = step as u128;
+                reward * (elapsed / step * step) / duration
+            }
+            // Divide between multiplications, each product fits u128 as elapsed < duration
+            RewardCurve::BackLoaded => reward * elapsed / duration * elapsed / duration,
+        };
+        vested as u64
+    }
+}
+
+/// Change of reward tier by authority
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum TierUpdate {
+    /// New settings for tier without stakers
+    Config {
+        supply: u16,
+        min_stake: u64,
+        max_stake: u64,
+        duration: u64,
+        reward_rate: u64,
+        curve: RewardCurve,
+        penalty: u8,
+        forfeit_reward: bool,
+        max_per_wallet: u16,
+    },
+    /// Add slots to tier
+    AddSlots { slots: u16 },
+    /// Stop new stakes to tier
+    Retire,
+}
+
+/// Admin roles which can be assigned to keys other than the authority
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum Role {
+    /// Pause, the authority can pause as well
+    Pauser,
+    /// Unpause, open and close
+    Operator,
+    /// Withdraw extra, free users and pool
+    Treasurer,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default, Debug)]
 pub struct Metrics {
     /// The required amount for reward claims
     pub reward_requirements: u64,
     /// The total paid rewards
     pub reward_paid: u64,
+    /// The staked amount held in vault
+    pub staked: u64,
+    /// The total funded rewards
+    pub funded: u64,
 }
 
 impl Metrics {
-    pub fn stake(&mut self, reward: u64) {
+    pub fn stake(&mut self, amount: u64, reward: u64) {
+        self.staked += amount;
         self.reward_requirements += reward;
     }
 
+    pub fn unstake(&mut self, amount: u64) {
+        self.staked -= amount;
+    }
+
+    pub fn fund(&mut self, amount: u64) {
+        self.funded += amount;
+    }
+
     pub fn claim(&mut self, amount: u64) {
         self.reward_paid += amount;
     }
+
+    /// Reward required for claims which is not paid yet
+    pub fn unpaid_reward(&self) -> u64 {
+        self.reward_requirements.saturating_sub(self.reward_paid)
+    }
+
+    /// Release reward which will never be paid
+    pub fn forfeit(&mut self, reward: u64) {
+        self.reward_requirements -= reward;
+    }
+}
+
+/// Keys of pool vault
+pub struct VaultKeys {
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub vault: Pubkey,
 }
 
 pub trait PoolAccount {
@@ -106,15 +428,25 @@ pub trait PoolAccount {
         &mut self,
         authority: Pubkey,
         bumps: &BTreeMap<String, u8>,
-        vault: Pubkey,
-        reward_vault: Pubkey,
-        tiers: [RewardTier; 3],
+        vault: VaultKeys,
+        reward_vault: VaultKeys,
+        tiers: Vec<RewardTier>,
     ) -> Result<()>;
 
     fn pause(&mut self) -> Result<()>;
     fn unpause(&mut self) -> Result<()>;
     fn open(&mut self) -> Result<()>;
     fn close(&mut self) -> Result<()>;
+    fn set_treasury(&mut self, treasury: Pubkey) -> Result<()>;
+    fn set_staking_window(&mut self, opens_at: Option<u64>, closes_at: Option<u64>) -> Result<()>;
+    fn set_merkle_root(&mut self, merkle_root: Option<[u8; 32]>) -> Result<()>;
+    fn set_solvency_check(&mut self, enabled: bool) -> Result<()>;
+    fn set_funding_cap(&mut self, enabled: bool) -> Result<()>;
+    fn propose_authority(&mut self, pending_authority: Option<Pubkey>) -> Result<()>;
+    fn accept_authority(&mut self) -> Result<()>;
+    fn set_role(&mut self, role: Role, key: Option<Pubkey>) -> Result<()>;
+    fn set_withdraw_delay(&mut self, withdraw_delay: u64) -> Result<()>;
+    fn enable_emergency(&mut self) -> Result<()>;
 }
 
 impl PoolAccount for Account<'_, Pool> {
@@ -122,32 +454,53 @@ impl PoolAccount for Account<'_, Pool> {
         &mut self,
         authority: Pubkey,
         bumps: &BTreeMap<String, u8>,
-        vault: Pubkey,
-        reward_vault: Pubkey,
-        tiers: [RewardTier; 3],
+        vault: VaultKeys,
+        reward_vault: VaultKeys,
+        tiers: Vec<RewardTier>,
     ) -> Result<()> {
         self.authority = authority;
+        self.pending_authority = None;
+        self.pauser = None;
+        self.operator = None;
+        self.treasurer = None;
         self.bump = *bumps
             .get("pool_signer")
             .ok_or_else(|| error!(StakingError::BumpFailure))?;
         self.paused = false;
         self.closed = false;
-        self.vault = vault;
+        self.opens_at = None;
+        self.closes_at = None;
+        self.merkle_root = None;
+        self.solvency_check = false;
+        self.funding_cap = false;
+        self.emergency = false;
+        self.mint = vault.mint;
+        self.token_program = vault.token_program;
+        self.vault = vault.vault;
         self.vault_bump = *bumps
             .get("vault")
             .ok_or_else(|| error!(StakingError::BumpFailure))?;
-        self.reward_vault = reward_vault;
+        self.reward_mint = reward_vault.mint;
+        self.reward_token_program = reward_vault.token_program;
+        self.reward_vault = reward_vault.vault;
         self.reward_vault_bump = *bumps
             .get("reward_vault")
             .ok_or_else(|| error!(StakingError::BumpFailure))?;
+        self.treasury = Pubkey::default();
+        self.withdraw_delay = 0;
+        self.next_withdrawal = 0;
 
+        require!(
+            !tiers.is_empty() && tiers.len() <= MAX_TIERS,
+            StakingError::InvalidRewardTier
+        );
         require!(
             tiers.iter().all(|tier| tier.check()),
             StakingError::InvalidRewardTier
         );
 
         self.tiers = tiers;
-        Ok(())
+        self.check_penalties()
     }
 
     fn pause(&mut self) -> Result<()> {
@@ -169,4 +522,71 @@ impl PoolAccount for Account<'_, Pool> {
         self.closed = true;
         Ok(())
     }
+
+    fn set_treasury(&mut self, treasury: Pubkey) -> Result<()> {
+        self.treasury = treasury;
+        Ok(())
+    }
+
+    fn set_staking_window(&mut self, opens_at: Option<u64>, closes_at: Option<u64>) -> Result<()> {
+        if let (Some(opens_at), Some(closes_at)) = (opens_at, closes_at) {
+            require!(opens_at < closes_at, StakingError::InvalidStakingWindow);
+        }
+        self.opens_at = opens_at;
+        self.closes_at = closes_at;
+        Ok(())
+    }
+
+    fn set_merkle_root(&mut self, merkle_root: Option<[u8; 32]>) -> Result<()> {
+        self.merkle_root = merkle_root;
+        Ok(())
+    }
+
+    fn set_solvency_check(&mut self, enabled: bool) -> Result<()> {
+        self.solvency_check = enabled;
+        Ok(())
+    }
+
+    fn set_funding_cap(&mut self, enabled: bool) -> Result<()> {
+        self.funding_cap = enabled;
+        Ok(())
+    }
+
+    fn propose_authority(&mut self, pending_authority: Option<Pubkey>) -> Result<()> {
+        self.pending_authority = pending_authority;
+        Ok(())
+    }
+
+    fn accept_authority(&mut self) -> Result<()> {
+        self.authority = self
+            .pending_authority
+            .take()
+            .ok_or_else(|| error!(StakingError::NotPendingAuthority))?;
+        Ok(())
+    }
+
+    fn set_role(&mut self, role: Role, key: Option<Pubkey>) -> Result<()> {
+        match role {
+            Role::Pauser => self.pauser = key,
+            Role::Operator => self.operator = key,
+            Role::Treasurer => self.treasurer = key,
+        }
+        Ok(())
+    }
+
+    fn set_withdraw_delay(&mut self, withdraw_delay: u64) -> Result<()> {
+        // Lowering the delay would enable instant withdraw_extra and withdraw_amount again
+        require!(
+            withdraw_delay >= self.withdraw_delay && withdraw_delay <= MAX_WITHDRAW_DELAY,
+            StakingError::InvalidWithdrawDelay
+        );
+        self.withdraw_delay = withdraw_delay;
+        Ok(())
+    }
+
+    fn enable_emergency(&mut self) -> Result<()> {
+        self.emergency = true;
+        self.closed = true;
+        Ok(())
+    }
 }
@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::*;
+use crate::RewardTier;
+use std::mem::size_of;
+
+/// Stake in a reward tier, PDA of user and position index
+#[account]
+pub struct Position {
+    /// Pool this position belongs to.
+    pub pool: Pubkey,
+    /// User account owning this position
+    pub user: Pubkey,
+    /// Index of position in user account
+    pub index: u64,
+    /// Reward tier of the stake
+    pub tier: u8,
+    /// The locked period
+    pub status: StakeStatus,
+    /// Signer bump
+    pub bump: u8,
+    /// Bump of receipt mint, PDA of position
+    pub receipt_bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum StakeStatus {
+    Staking {
+        locked_until: u64,
+        last_claimed: u64,
+        reward_paid: u64,
+        /// Staked amount received by vault
+        amount: u64,
+        /// Total reward for duration
+        reward: u64,
+    },
+    Ready {
+        amount: u64,
+    },
+}
+
+impl StakeStatus {
+    pub fn new_stake(locked_until: u64, amount: u64, reward: u64) -> Result<Self> {
+        let clock = clock::Clock::get()?;
+        Ok(StakeStatus::Staking {
+            locked_until,
+            last_claimed: clock.unix_timestamp as u64,
+            reward_paid: 0,
+            amount,
+            reward,
+        })
+    }
+
+    /// Calc reword for tier, update StakeStatus numbers
+    pub fn update_reword(&self, tier: &RewardTier, now: u64) -> (u64, StakeStatus) {
+        match self {
+            StakeStatus::Staking {
+                locked_until,
+                reward_paid,
+                amount: staked,
+                reward,
+                ..
+            } => {
+                if now >= *locked_until {
+                    (reward - reward_paid, StakeStatus::Ready { amount: *staked })
+                } else {
+                    let elapsed = now.saturating_sub(locked_until - tier.duration);
+                    let remaining = reward - reward_paid;
+                    let amount = tier
+                        .curve
+                        .vested(*reward, elapsed, tier.duration)
+                        .saturating_sub(*reward_paid);
+                    if amount == remaining {
+                        (amount, StakeStatus::Ready { amount: *staked })
+                    } else {
+                        (
+                            amount,
+                            StakeStatus::Staking {
+                                locked_until: *locked_until,
+                                last_claimed: now,
+                                reward_paid: reward_paid + amount,
+                                amount: *staked,
+                                reward: *reward,
+                            },
+                        )
+                    }
+                }
+            }
+            _ => (0, *self),
+        }
+    }
+}
+
+impl Position {
+    pub fn space() -> usize {
+        8 + size_of::<Position>()
+    }
+}
+
+pub trait PositionAccount {
+    #[allow(clippy::too_many_arguments)]
+    fn init(
+        &mut self,
+        pool: Pubkey,
+        user: Pubkey,
+        index: u64,
+        tier: u8,
+        status: StakeStatus,
+        bump: u8,
+        receipt_bump: u8,
+    ) -> Result<()>;
+    fn receipt_mint(&self) -> Result<Pubkey>;
+}
+
+impl PositionAccount for Account<'_, Position> {
+    fn init(
+        &mut self,
+        pool: Pubkey,
+        user: Pubkey,
+        index: u64,
+        tier: u8,
+        status: StakeStatus,
+        bump: u8,
+        receipt_bump: u8,
+    ) -> Result<()> {
+        self.pool = pool;
+        self.user = user;
+        self.index = index;
+        self.tier = tier;
+        self.status = status;
+        self.bump = bump;
+        self.receipt_bump = receipt_bump;
+
+        Ok(())
+    }
+
+    fn receipt_mint(&self) -> Result<Pubkey> {
+        Pubkey::create_program_address(
+            &[
+                b"receipt".as_ref(),
+                self.key().as_ref(),
+                &[self.receipt_bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| error!(StakingError::BumpFailure))
+    }
+}
+
+/// Token account holds the position receipt and belongs to owner
+pub fn holds_receipt(receipt: &TokenAccount, receipt_mint: &Pubkey, owner: &Pubkey) -> bool {
+    receipt.mint == *receipt_mint && receipt.owner == *owner && receipt.amount == 1
+}
+
+/// Load positions of user from remaining accounts, each position at most once and followed
+/// by its receipt token account, returns positions with receipt holders
+pub fn load_positions<'info>(
+    accounts: &[AccountInfo<'info>],
+    user: &Pubkey,
+) -> Result<Vec<(Account<'info, Position>, Pubkey)>> {
+    let pairs = accounts.chunks_exact(2);
+    require!(pairs.remainder().is_empty(), StakingError::InvalidPosition);
+
+    let mut positions: Vec<(Account<'info, Position>, Pubkey)> = Vec::with_capacity(pairs.len());
+    for pair in pairs {
+        require!(pair[0].is_writable, StakingError::InvalidPosition);
+        let position = Account::<Position>::try_from(&pair[0])?;
+        require!(
+            position.user == *user && positions.iter().all(|(p, _)| p.key() != position.key()),
+            StakingError::InvalidPosition
+        );
+
+        let receipt = Account::<TokenAccount>::try_from(&pair[1])?;
+        require!(
+            receipt.mint == position.receipt_mint()? && receipt.amount == 1,
+            StakingError::NotReceiptHolder
+        );
+
+        positions.push((position, receipt.owner));
+    }
+    Ok(positions)
+}
@@ -1,7 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::clock;
 
-use crate::RewardTier;
+use crate::errors::*;
 use std::mem::size_of;
 
 #[account]
@@ -10,95 +9,83 @@ pub struct User {
     pub pool: Pubkey,
     /// The owner/authority of this account
     pub authority: Pubkey,
-    /// The locked periods
-    pub stakes: [StakeStatus; 3],
+    /// Index of the next position, part of position PDA seeds
+    pub next_position: u64,
+    /// Number of open positions, one counter per pool tier
+    pub positions: Vec<u16>,
     /// Signer bump
     pub bump: u8,
+    /// Optional key allowed to claim rewards on behalf of the authority
+    pub delegate: Option<Pubkey>,
+    /// Optional token account all rewards must be sent to
+    pub reward_destination: Option<Pubkey>,
+    /// Max positions per tier granted by the pool allowlist, 0 for no limit
+    pub allowance: u16,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
-pub enum StakeStatus {
-    None,
-    Staking {
-        locked_until: u64,
-        last_claimed: u64,
-        reward_paid: u64,
-    },
-    Ready,
-    Used,
-}
+impl User {
+    pub fn space(tiers: usize) -> usize {
+        8 + size_of::<User>() + tiers * size_of::<u16>()
+    }
 
-impl StakeStatus {
-    pub fn is_none(&self) -> bool {
-        matches!(self, StakeStatus::None)
+    /// Open position in tier, returns index of the new position
+    pub fn open_position(&mut self, tier: u8, max_per_wallet: u16) -> Result<u64> {
+        let positions = &mut self.positions[tier as usize];
+        require!(
+            [max_per_wallet, self.allowance]
+                .iter()
+                .all(|limit| *limit == 0 || *positions < *limit),
+            StakingError::TierCapReached
+        );
+        *positions += 1;
+
+        let index = self.next_position;
+        self.next_position += 1;
+        Ok(index)
     }
 
-    pub fn new_stake(locked_until: u64) -> Result<Self> {
-        let clock = clock::Clock::get()?;
-        Ok(StakeStatus::Staking {
-            locked_until,
-            last_claimed: clock.unix_timestamp as u64,
-            reward_paid: 0,
-        })
+    pub fn close_position(&mut self, tier: u8) {
+        self.positions[tier as usize] -= 1;
     }
 
-    /// Calc reword for tier, update StakeStatus numbers
-    pub fn update_reword(&self, tier: &RewardTier, now: u64) -> (u64, StakeStatus) {
-        match self {
-            StakeStatus::Staking {
-                locked_until,
-                last_claimed,
-                reward_paid,
-            } => {
-                if now >= *locked_until {
-                    (tier.reward - reward_paid, StakeStatus::Ready)
-                } else {
-                    let time_passed = now - last_claimed;
-                    let remaining = tier.reward - reward_paid;
-                    let amount = remaining.min(
-                        ((tier.reward as u128 * time_passed as u128) / tier.duration as u128)
-                            as u64,
-                    );
-                    if amount == remaining {
-                        (amount, StakeStatus::Ready)
-                    } else {
-                        (
-                            amount,
-                            StakeStatus::Staking {
-                                locked_until: *locked_until,
-                                last_claimed: now,
-                                reward_paid: reward_paid + amount,
-                            },
-                        )
-                    }
-                }
-            }
-            _ => (0, *self),
-        }
+    pub fn has_positions(&self) -> bool {
+        self.positions.iter().any(|positions| *positions > 0)
     }
-}
 
-impl Default for StakeStatus {
-    fn default() -> Self {
-        StakeStatus::None
+    /// Receipt holder can claim rewards, the delegate only for receipts held by the authority
+    pub fn can_claim(&self, signer: &Pubkey, holder: &Pubkey) -> bool {
+        holder == signer || (*holder == self.authority && self.delegate == Some(*signer))
     }
-}
 
-impl User {
-    pub fn space() -> usize {
-        size_of::<User>()
+    /// Check reward token account against the reward destination, if set
+    /// and the receipt is held by the authority
+    pub fn allows_reward_to(&self, to: &Pubkey, holder: &Pubkey) -> bool {
+        *holder != self.authority
+            || self.reward_destination.is_none()
+            || self.reward_destination == Some(*to)
+    }
+
+    /// The delegate can claim only to the reward destination of the authority
+    pub fn allows_claim_to(&self, to: &Pubkey, signer: &Pubkey, holder: &Pubkey) -> bool {
+        self.allows_reward_to(to, holder)
+            && (signer == holder || self.reward_destination == Some(*to))
     }
 }
 
 pub trait UserAccount {
-    fn init(&mut self, pool: Pubkey, authority: Pubkey, bump: u8) -> Result<()>;
+    fn init(&mut self, pool: Pubkey, authority: Pubkey, bump: u8, tiers: usize) -> Result<()>;
 }
 
 impl UserAccount for Account<'_, User> {
-    fn init(&mut self, pool: Pubkey, authority: Pubkey, bump: u8) -> Result<()> {
+    fn init(&mut self, pool: Pubkey, authority: Pubkey, bump: u8, tiers: usize) -> Result<()> {
         self.pool = pool;
         self.authority = authority;
+        self.next_position = 0;
+        self.positions = vec![0; tiers];
         self.bump = bump;
+        self.delegate = None;
+        self.reward_destination = None;
+        self.allowance = 0;
 
         Ok(())
     }
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+/// Pool vault selector
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum VaultKind {
+    /// Vault of staked tokens
+    Stake,
+    /// Vault of reward tokens
+    Reward,
+}
+
+/// Withdrawal from pool vault queued by treasurer, PDA of pool and withdrawal index
+#[account]
+pub struct Withdrawal {
+    /// Pool this withdrawal belongs to.
+    pub pool: Pubkey,
+    /// Index of withdrawal in pool
+    pub index: u64,
+    /// Vault the tokens are withdrawn from
+    pub vault: VaultKind,
+    /// Withdrawn amount
+    pub amount: u64,
+    /// Destination token account
+    pub to: Pubkey,
+    /// Earliest time the withdrawal can be executed
+    pub executable_at: u64,
+    /// Signer bump
+    pub bump: u8,
+}
+
+impl Withdrawal {
+    pub fn space() -> usize {
+        8 + size_of::<Withdrawal>()
+    }
+}
+
+pub trait WithdrawalAccount {
+    #[allow(clippy::too_many_arguments)]
+    fn init(
+        &mut self,
+        pool: Pubkey,
+        index: u64,
+        vault: VaultKind,
+        amount: u64,
+        to: Pubkey,
+        executable_at: u64,
+        bump: u8,
+    ) -> Result<()>;
+}
+
+impl WithdrawalAccount for Account<'_, Withdrawal> {
+    fn init(
+        &mut self,
+        pool: Pubkey,
+        index: u64,
+        vault: VaultKind,
+        amount: u64,
+        to: Pubkey,
+        executable_at: u64,
+        bump: u8,
+    ) -> Result<()> {
+        self.pool = pool;
+        self.index = index;
+        self.vault = vault;
+        self.amount = amount;
+        self.to = to;
+        self.executable_at = executable_at;
+        self.bump = bump;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,450 @@
+//! Token operations working with both SPL Token and Token-2022 programs
+//!
+//! Token-2022 shares base state layout and instructions with SPL Token, extensions are
+//! stored as type-length-value entries after the base state. Only the transfer fee
+//! extensions are read here.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::program_pack::{IsInitialized, Pack};
+use anchor_lang::solana_program::{system_instruction, sysvar};
+use anchor_spl::token::spl_token;
+use spl_token::instruction::AuthorityType;
+use spl_token::state::{Account, Mint};
+
+use crate::errors::StakingError;
+
+pub mod token_2022 {
+    anchor_lang::declare_id!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+}
+
+/// Offset of Token-2022 account type, mints are padded to the token account size
+const ACCOUNT_TYPE_OFFSET: usize = Account::LEN;
+const ACCOUNT_TYPE_MINT: u8 = 1;
+const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+
+const EXTENSION_TRANSFER_FEE_CONFIG: u16 = 1;
+/// Length of TransferFeeAmount extension of token accounts
+const TRANSFER_FEE_AMOUNT_LEN: usize = 8;
+
+/// Token-2022 instruction with transfer fee extension instructions
+const TRANSFER_FEE_EXTENSION: u8 = 26;
+const HARVEST_WITHHELD_TOKENS_TO_MINT: u8 = 4;
+
+pub fn is_token_program(key: &Pubkey) -> bool {
+    *key == anchor_spl::token::ID || *key == token_2022::ID
+}
+
+/// Unpack base state, extended data is allowed only after Token-2022 account type of the state
+fn unpack<T: Pack + IsInitialized>(data: &[u8], account_type: u8) -> Result<T> {
+    if data.len() != T::LEN && data.get(ACCOUNT_TYPE_OFFSET) != Some(&account_type) {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+    T::unpack(&data[..T::LEN]).map_err(Into::into)
+}
+
+/// Value of Token-2022 extension of the type, if any
+fn extension(data: &[u8], extension_type: u16) -> Option<&[u8]> {
+    let mut tlv = data.get(ACCOUNT_TYPE_OFFSET + 1..)?;
+    while tlv.len() >= 4 {
+        let entry_type = u16::from_le_bytes([tlv[0], tlv[1]]);
+        let len = u16::from_le_bytes([tlv[2], tlv[3]]) as usize;
+        let value = tlv.get(4..4 + len)?;
+        if entry_type == extension_type {
+            return Some(value);
+        }
+        tlv = &tlv[4 + len..];
+    }
+    None
+}
+
+/// Fee of TransferFeeConfig extension for epoch, rounded up and capped by maximum fee
+fn epoch_fee(config: &[u8], epoch: u64, amount: u64) -> Option<u64> {
+    // Fee authorities and withheld amount precede the older and the newer transfer fee,
+    // each is epoch, maximum fee and basis points
+    let transfer_fee = |offset: usize| -> Option<(u64, u64, u16)> {
+        let fee = config.get(offset..offset + 18)?;
+        Some((
+            u64::from_le_bytes(fee[..8].try_into().ok()?),
+            u64::from_le_bytes(fee[8..16].try_into().ok()?),
+            u16::from_le_bytes(fee[16..].try_into().ok()?),
+        ))
+    };
+    let older = transfer_fee(72)?;
+    let newer = transfer_fee(90)?;
+    let (_, maximum_fee, basis_points) = if epoch >= newer.0 { newer } else { older };
+
+    if basis_points == 0 || amount == 0 {
+        return Some(0);
+    }
+    let fee = (amount as u128 * basis_points as u128 + 9_999) / 10_000;
+    u64::try_from(fee).ok().map(|fee| fee.min(maximum_fee))
+}
+
+/// SPL Token instruction sent to token program, Token-2022 uses the same layout
+fn for_program(mut ix: Instruction, token_program: &Pubkey) -> Instruction {
+    ix.program_id = *token_program;
+    ix
+}
+
+/// Balance of token account
+pub fn amount(account: &AccountInfo) -> Result<u64> {
+    let data = account.try_borrow_data()?;
+    Ok(unpack::<Account>(&data, ACCOUNT_TYPE_ACCOUNT)?.amount)
+}
+
+/// Mint of token account
+pub fn mint(account: &AccountInfo) -> Result<Pubkey> {
+    let data = account.try_borrow_data()?;
+    Ok(unpack::<Account>(&data, ACCOUNT_TYPE_ACCOUNT)?.mint)
+}
+
+/// Fee withheld from the transferred amount in current epoch
+pub fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let data = mint.try_borrow_data()?;
+    unpack::<Mint>(&data, ACCOUNT_TYPE_MINT)?;
+    match extension(&data, EXTENSION_TRANSFER_FEE_CONFIG) {
+        Some(config) => epoch_fee(config, Clock::get()?.epoch, amount)
+            .ok_or_else(|| error!(StakingError::CalcFailure)),
+        None => Ok(0),
+    }
+}
+
+pub fn transfer_checked<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let decimals = {
+        let data = mint.try_borrow_data()?;
+        unpack::<Mint>(&data, ACCOUNT_TYPE_MINT)?.decimals
+    };
+    let ix = for_program(
+        spl_token::instruction::transfer_checked(
+            &spl_token::ID,
+            from.key,
+            mint.key,
+            to.key,
+            authority.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+        token_program.key,
+    );
+    invoke_signed(
+        &ix,
+        &[
+            from.clone(),
+            mint.clone(),
+            to.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+pub fn close_account<'info>(
+    token_program: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let ix = for_program(
+        spl_token::instruction::close_account(
+            &spl_token::ID,
+            account.key,
+            destination.key,
+            authority.key,
+            &[],
+        )?,
+        token_program.key,
+    );
+    invoke_signed(
+        &ix,
+        &[
+            account.clone(),
+            destination.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Move transfer fees withheld in account to mint, so the account can be closed
+pub fn harvest_withheld_fees<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+) -> Result<()> {
+    let has_fee = {
+        let data = mint.try_borrow_data()?;
+        extension(&data, EXTENSION_TRANSFER_FEE_CONFIG).is_some()
+    };
+    if !has_fee {
+        return Ok(());
+    }
+
+    let ix = Instruction {
+        program_id: *token_program.key,
+        accounts: vec![
+            AccountMeta::new(*mint.key, false),
+            AccountMeta::new(*account.key, false),
+        ],
+        data: vec![TRANSFER_FEE_EXTENSION, HARVEST_WITHHELD_TOKENS_TO_MINT],
+    };
+    invoke_signed(
+        &ix,
+        &[mint.clone(), account.clone(), token_program.clone()],
+        &[],
+    )
+    .map_err(Into::into)
+}
+
+/// Create PDA account owned by program. Anyone can send lamports to the address ahead,
+/// so a funded account is topped up to rent exemption, allocated and assigned instead
+fn create_pda<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    system_program: &AccountInfo<'info>,
+    rent: &Sysvar<'info, Rent>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let lamports = account.lamports();
+    if lamports == 0 {
+        return invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                account.key,
+                rent.minimum_balance(space),
+                space as u64,
+                owner,
+            ),
+            &[payer.clone(), account.clone(), system_program.clone()],
+            signer_seeds,
+        )
+        .map_err(Into::into);
+    }
+
+    let required = rent.minimum_balance(space).saturating_sub(lamports);
+    if required > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, required),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(account.key, space as u64),
+        &[account.clone(), system_program.clone()],
+        signer_seeds,
+    )?;
+    invoke_signed(
+        &system_instruction::assign(account.key, owner),
+        &[account.clone(), system_program.clone()],
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Create token account at PDA with space for extensions required by mint
+#[allow(clippy::too_many_arguments)]
+pub fn create_account<'info>(
+    token_program: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    rent: &Sysvar<'info, Rent>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    // Mints with transfer fee require the withheld amount extension in token accounts
+    let space = {
+        let data = mint.try_borrow_data()?;
+        if extension(&data, EXTENSION_TRANSFER_FEE_CONFIG).is_some() {
+            ACCOUNT_TYPE_OFFSET + 1 + 4 + TRANSFER_FEE_AMOUNT_LEN
+        } else {
+            Account::LEN
+        }
+    };
+
+    create_pda(
+        payer,
+        account,
+        space,
+        token_program.key,
+        system_program,
+        rent,
+        signer_seeds,
+    )?;
+
+    let ix = for_program(
+        spl_token::instruction::initialize_account(
+            &spl_token::ID,
+            account.key,
+            mint.key,
+            owner.key,
+        )?,
+        token_program.key,
+    );
+    invoke_signed(
+        &ix,
+        &[
+            account.clone(),
+            mint.clone(),
+            owner.clone(),
+            rent.to_account_info(),
+            token_program.clone(),
+        ],
+        &[],
+    )
+    .map_err(Into::into)
+}
+
+/// Create receipt mint at PDA and mint its only token to associated token account of owner,
+/// mint authority is removed afterwards so supply stays 1
+#[allow(clippy::too_many_arguments)]
+pub fn create_receipt<'info>(
+    token_program: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    rent: &Sysvar<'info, Rent>,
+    mint_seeds: &[&[u8]],
+    authority_seeds: &[&[u8]],
+) -> Result<()> {
+    create_pda(
+        payer,
+        mint,
+        Mint::LEN,
+        token_program.key,
+        system_program,
+        rent,
+        &[mint_seeds],
+    )?;
+
+    let ix = for_program(
+        spl_token::instruction::initialize_mint(
+            &spl_token::ID,
+            mint.key,
+            mint_authority.key,
+            None,
+            0,
+        )?,
+        token_program.key,
+    );
+    invoke_signed(
+        &ix,
+        &[mint.clone(), rent.to_account_info(), token_program.clone()],
+        &[],
+    )?;
+
+    let ix = Instruction {
+        program_id: *associated_token_program.key,
+        accounts: vec![
+            AccountMeta::new(*payer.key, true),
+            AccountMeta::new(*account.key, false),
+            AccountMeta::new_readonly(*owner.key, false),
+            AccountMeta::new_readonly(*mint.key, false),
+            AccountMeta::new_readonly(*system_program.key, false),
+            AccountMeta::new_readonly(*token_program.key, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ],
+        data: vec![],
+    };
+    invoke_signed(
+        &ix,
+        &[
+            payer.clone(),
+            account.clone(),
+            owner.clone(),
+            mint.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            rent.to_account_info(),
+            associated_token_program.clone(),
+        ],
+        &[],
+    )?;
+
+    let ix = for_program(
+        spl_token::instruction::mint_to(
+            &spl_token::ID,
+            mint.key,
+            account.key,
+            mint_authority.key,
+            &[],
+            1,
+        )?,
+        token_program.key,
+    );
+    invoke_signed(
+        &ix,
+        &[
+            mint.clone(),
+            account.clone(),
+            mint_authority.clone(),
+            token_program.clone(),
+        ],
+        &[authority_seeds],
+    )?;
+
+    let ix = for_program(
+        spl_token::instruction::set_authority(
+            &spl_token::ID,
+            mint.key,
+            None,
+            AuthorityType::MintTokens,
+            mint_authority.key,
+            &[],
+        )?,
+        token_program.key,
+    );
+    invoke_signed(
+        &ix,
+        &[mint.clone(), mint_authority.clone(), token_program.clone()],
+        &[authority_seeds],
+    )
+    .map_err(Into::into)
+}
+
+/// Burn receipt held by owner and close the emptied token account to owner
+pub fn burn_receipt<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+) -> Result<()> {
+    let ix = for_program(
+        spl_token::instruction::burn(&spl_token::ID, account.key, mint.key, owner.key, &[], 1)?,
+        token_program.key,
+    );
+    invoke_signed(
+        &ix,
+        &[
+            account.clone(),
+            mint.clone(),
+            owner.clone(),
+            token_program.clone(),
+        ],
+        &[],
+    )?;
+
+    close_account(token_program, account, owner, owner, &[])
+}
@@ -0,0 +1,126 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("allowlist", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const provider = anchor.getProvider();
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+  const otherMint = Keypair.generate();
+
+  const tiers = [
+    {
+      supply: 3,
+      slots: 3,
+      minStake: new BN(5_000_000),
+      maxStake: new BN(5_000_000),
+      duration: new BN(5),
+      rewardRate: new BN(1_000_000_000),
+      curve: { linear: {} },
+    },
+  ];
+
+  before(async() => {
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+    await creatMintIfRequired(spl_program, otherMint, provider.wallet.publicKey);
+  });
+
+  it("Should initialize pool with any mint if there is no allowlist", async () => {
+    await initializeProgram(program, Keypair.generate(), authority.publicKey,
+      provider.wallet.publicKey, otherMint.publicKey, tiers);
+  });
+
+  it("Should NOT create allowlist if not upgrade authority", async () => {
+    const [programData, _nonce] = await PublicKey.findProgramAddress(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+
+    await expect(program.methods.createAllowlist([mint.publicKey])
+      .accounts({
+        authority: authority.publicKey,
+        program: program.programId,
+        programData,
+      })
+      .signers([authority])
+      .rpc()).to.be.rejected;
+  });
+
+  it("Should create allowlist", async () => {
+    const [programData, _nonce] = await PublicKey.findProgramAddress(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+
+    await program.methods.createAllowlist([mint.publicKey])
+      .accounts({
+        authority: provider.wallet.publicKey,
+        program: program.programId,
+        programData,
+      })
+      .rpc();
+
+    const [allowlist, _nonce2] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("allowlist")],
+      program.programId
+    );
+    const allowlistAccount = await program.account.allowlist.fetch(allowlist);
+    expect(allowlistAccount.mints).to.be.deep.equal([mint.publicKey]);
+  });
+
+  it("Should initialize pool with allowed mint", async () => {
+    const pool = Keypair.generate();
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.mint).to.be.deep.equal(mint.publicKey);
+  });
+
+  it("Should NOT initialize pool with not allowed mint", async () => {
+    await expect(initializeProgram(program, Keypair.generate(), authority.publicKey,
+      provider.wallet.publicKey, otherMint.publicKey, tiers)).to.be.rejectedWith(/The mint is not allowed/);
+  });
+
+  it("Should update allowlist", async () => {
+    await program.methods.setAllowlist([mint.publicKey, otherMint.publicKey])
+      .accounts({
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    await initializeProgram(program, Keypair.generate(), authority.publicKey,
+      provider.wallet.publicKey, otherMint.publicKey, tiers);
+  });
+
+  it("Should close allowlist", async () => {
+    await program.methods.closeAllowlist()
+      .accounts({
+        authority: provider.wallet.publicKey,
+        receiver: provider.wallet.publicKey,
+      })
+      .rpc();
+  });
+});
@@ -0,0 +1,136 @@
+import * as anchor from "@project-serum/anchor";
+import { Keypair } from '@solana/web3.js';
+import { Program, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  pause, unpause
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("authority transfer", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+  const newAuthority = Keypair.generate();
+  const stranger = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  before(async () => {
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+
+    const tiers = [
+      {
+        supply: 3,
+        slots: 3,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(600_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+  });
+
+  async function acceptAuthority(signer: Keypair) {
+    return program.methods.acceptAuthority()
+      .accounts({
+        pool: pool.publicKey,
+        pendingAuthority: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  it("Should NOT propose authority by non authority", async () => {
+    await expect(program.methods.proposeAuthority(stranger.publicKey)
+      .accounts({
+        pool: pool.publicKey,
+        authority: stranger.publicKey,
+      })
+      .signers([stranger])
+      .rpc()).to.be.rejectedWith(/A has one constraint was violated/);
+  });
+
+  it("Should NOT accept authority without proposal", async () => {
+    await expect(acceptAuthority(newAuthority)).to.be.rejectedWith(/Signer is not the pending authority/);
+  });
+
+  it("Should propose authority", async () => {
+    await program.methods.proposeAuthority(newAuthority.publicKey)
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.authority).to.be.deep.equal(authority.publicKey);
+    expect(poolAccount.pendingAuthority).to.be.deep.equal(newAuthority.publicKey);
+  });
+
+  it("Should NOT accept authority by other than proposed", async () => {
+    await expect(acceptAuthority(stranger)).to.be.rejectedWith(/Signer is not the pending authority/);
+  });
+
+  it("Should accept authority by proposed", async () => {
+    await acceptAuthority(newAuthority);
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.authority).to.be.deep.equal(newAuthority.publicKey);
+    expect(poolAccount.pendingAuthority).to.be.null;
+  });
+
+  it("Should restrict admin actions to the new authority", async () => {
+    await expect(pause(program, pool.publicKey, authority)).to.be.rejectedWith(/Signer doesn't have the role for this action/);
+
+    await pause(program, pool.publicKey, newAuthority);
+    await unpause(program, pool.publicKey, newAuthority);
+  });
+
+  it("Should cancel proposal", async () => {
+    await program.methods.proposeAuthority(stranger.publicKey)
+      .accounts({
+        pool: pool.publicKey,
+        authority: newAuthority.publicKey,
+      })
+      .signers([newAuthority])
+      .rpc();
+
+    await program.methods.proposeAuthority(null)
+      .accounts({
+        pool: pool.publicKey,
+        authority: newAuthority.publicKey,
+      })
+      .signers([newAuthority])
+      .rpc();
+
+    await expect(acceptAuthority(stranger)).to.be.rejectedWith(/Signer is not the pending authority/);
+  });
+});
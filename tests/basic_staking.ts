@@ -11,7 +11,7 @@ import {
   creatMintIfRequired,
   createToken,
   mintTo,
-  mintToATA, tokenBalance, stakingAccount, waitUntilblockTime, blockTimeFromTx, vaultBalance, rewardBalance, getATA
+  mintToATA, tokenBalance, stakingAccount, positionAddress, receiptAccounts, positionAccount, positionMetas, waitUntilblockTime, blockTimeFromTx, vaultBalance, rewardBalance, getATA
 } from './utils';
 
 import { expect } from 'chai';
@@ -58,23 +58,29 @@ describe("basic staking", () => {
       {
         supply: 3,
         slots: 3,
-        stake: new BN(5_000_000),
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
         duration: new BN(5),
-        reward: new BN(5_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       },
       {
         supply: 3,
         slots: 3,
-        stake: new BN(10_000_000),
+        minStake: new BN(10_000_000),
+        maxStake: new BN(10_000_000),
         duration: new BN(10),
-        reward: new BN(10_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       },
       {
         supply: 3,
         slots: 3,
-        stake: new BN(15_000_000),
+        minStake: new BN(15_000_000),
+        maxStake: new BN(15_000_000),
         duration: new BN(15),
-        reward: new BN(15_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       },
     ];
 
@@ -125,11 +131,13 @@ describe("basic staking", () => {
     let poolAccount = await program.account.pool.fetch(pool.publicKey);
     expect(poolAccount.tiers[0].slots).to.be.equal(3);
 
-    const tx = await program.methods.stake({tier500:{}})
+    const tx = await program.methods.stake(0, new BN(5_000_000))
       .accounts({
         pool: pool.publicKey,
         authority: user1.publicKey,
+        position: await positionAddress(program, pool.publicKey, user1.publicKey, 0),
         from: user1ata,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user1.publicKey, 0), user1.publicKey),
       })
       .preInstructions(
         [
@@ -149,46 +157,36 @@ describe("basic staking", () => {
     poolAccount = await program.account.pool.fetch(pool.publicKey);
     expect(poolAccount.tiers[0].slots).to.be.equal(2);
     expect(poolAccount.metrics.rewardRequirements.toNumber()).to.be.equal(5_000_000);
+    expect(poolAccount.metrics.staked.toNumber()).to.be.equal(5_000_000);
 
     expect(await vaultBalance(spl_program, pool.publicKey, program.programId)).to.be.equal(5_000_000);
 
-    const user1stakingAccount = await stakingAccount(program, pool.publicKey, user1.publicKey);
+    const position = await positionAccount(program, pool.publicKey, user1.publicKey, 0);
     const blockTime = await blockTimeFromTx(provider, tx);
 
-    expect(user1stakingAccount.stakes[0].staking.lastClaimed.toNumber()).to.be.equal(blockTime);
-    expect(user1stakingAccount.stakes[0].staking.lockedUntil.toNumber()).to.be.equal(blockTime + poolAccount.tiers[0].duration.toNumber());
+    expect(position.tier).to.be.equal(0);
+    expect(position.status.staking.lastClaimed.toNumber()).to.be.equal(blockTime);
+    expect(position.status.staking.lockedUntil.toNumber()).to.be.equal(blockTime + poolAccount.tiers[0].duration.toNumber());
   });
 
   it("Should NOT unstake before lock time", async () => {
     const ata = await getATA(user1.publicKey, mint.publicKey);
 
-    await expect(program.methods.unstake({tier500:{}})
+    await expect(program.methods.unstake()
       .accounts({
         pool: pool.publicKey,
         authority: user1.publicKey,
+        position: await positionAddress(program, pool.publicKey, user1.publicKey, 0),
         to: ata,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user1.publicKey, 0), user1.publicKey),
       })
       .signers([user1])
       .rpc()).to.be.rejectedWith(/The time lock has not yet passed/);
   })
 
-  it("Should NOT stake if already staked (StakeStatus == Staking { .. })", async() => {
-    const ata = await getATA(user1.publicKey, mint.publicKey);
-
-    const staking = await stakingAccount(program, pool.publicKey, user1.publicKey);
-    expect(staking.stakes[0]).to.have.property('staking');
-
-    await expect(program.methods.stake({tier500:{}})
-      .accounts({
-        pool: pool.publicKey,
-        authority: user1.publicKey,
-        from: ata,
-      }).signers([user1]).rpc())
-      .to.be.rejectedWith(/Tier already used/);
-  });
-
   it("Should NOT claim if there are no reward", async() => {
     const ata = await getATA(user1.publicKey, mint.publicKey);
+    const positions = await positionMetas(program, user1.publicKey, [await positionAddress(program, pool.publicKey, user1.publicKey, 0)]);
 
     await expect(
       program.methods.claim()
@@ -197,6 +195,7 @@ describe("basic staking", () => {
         authority: user1.publicKey,
         to: ata,
       })
+      .remainingAccounts(positions)
       .signers([user1])
       .postInstructions(
         [
@@ -205,13 +204,30 @@ describe("basic staking", () => {
               pool: pool.publicKey,
               authority: user1.publicKey,
               to: ata,
-            }).instruction()
+            })
+            .remainingAccounts(positions)
+            .instruction()
         ]
       )
       .rpc()
     ).to.be.rejectedWith(/Amount must be greater than zero/);
   });
 
+  it("Should NOT claim the same position twice", async () => {
+    const ata = await getATA(user1.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user1.publicKey, 0);
+
+    await expect(program.methods.claim()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user1.publicKey,
+        to: ata,
+      })
+      .remainingAccounts(await positionMetas(program, user1.publicKey, [position, position]))
+      .signers([user1])
+      .rpc()).to.be.rejectedWith(/Invalid position/);
+  });
+
   it("Should NOT claim if paused", async () => {
     const ata = await getATA(user1.publicKey, mint.publicKey);
 
@@ -223,6 +239,7 @@ describe("basic staking", () => {
         authority: user1.publicKey,
         to: ata,
       })
+      .remainingAccounts(await positionMetas(program, user1.publicKey, [await positionAddress(program, pool.publicKey, user1.publicKey, 0)]))
       .signers([user1])
       .rpc()).to.be.rejectedWith(/Pool is paused/);
 
@@ -233,16 +250,18 @@ describe("basic staking", () => {
   it("Should NOT unstake if pending reward", async () => {
     const ata = await getATA(user1.publicKey, mint.publicKey);
 
-    let staking = await stakingAccount(program, pool.publicKey, user1.publicKey);
-    const lockedUntil = staking.stakes[0].staking.lockedUntil.toNumber();
+    const position = await positionAccount(program, pool.publicKey, user1.publicKey, 0);
+    const lockedUntil = position.status.staking.lockedUntil.toNumber();
     await waitUntilblockTime(provider, lockedUntil + 2);
 
     await expect(
-      program.methods.unstake({tier500:{}})
+      program.methods.unstake()
       .accounts({
         pool: pool.publicKey,
         authority: user1.publicKey,
+        position: await positionAddress(program, pool.publicKey, user1.publicKey, 0),
         to: ata,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user1.publicKey, 0), user1.publicKey),
       })
       .signers([user1])
       .rpc()
@@ -254,8 +273,8 @@ describe("basic staking", () => {
     const user1ata = await getATA(user1.publicKey, mint.publicKey);
     const ataBalanceBefore = await tokenBalance(spl_program, user1ata);
 
-    let user1stakingAccount = await stakingAccount(program, pool.publicKey, user1.publicKey);
-    const lockedUntil = user1stakingAccount.stakes[0].staking.lockedUntil.toNumber();
+    let position = await positionAccount(program, pool.publicKey, user1.publicKey, 0);
+    const lockedUntil = position.status.staking.lockedUntil.toNumber();
 
     const rewardBalanceBefore = await rewardBalance(spl_program, pool.publicKey, program.programId);
 
@@ -267,6 +286,7 @@ describe("basic staking", () => {
         authority: user1.publicKey,
         to: user1ata,
       })
+      .remainingAccounts(await positionMetas(program, user1.publicKey, [await positionAddress(program, pool.publicKey, user1.publicKey, 0)]))
       .signers([user1])
       .rpc({commitment:'confirmed'});
 
@@ -278,37 +298,24 @@ describe("basic staking", () => {
     const rewardBalanceAfter = await rewardBalance(spl_program, pool.publicKey, program.programId);
     expect(rewardBalanceBefore - rewardBalanceAfter).to.be.equal(5_000_000);
 
-    user1stakingAccount = await stakingAccount(program, pool.publicKey, user1.publicKey);
-    expect(user1stakingAccount.stakes[0]).to.be.deep.equal({ready:{}});
-  });
-
-  it("Should NOT stake if already staked (StakeStatus == Ready)", async() => {
-    const ata = await getATA(user1.publicKey, mint.publicKey);
-
-    const staking = await stakingAccount(program, pool.publicKey, user1.publicKey);
-    expect(staking.stakes[0]).to.have.property('ready');
-
-    await expect(program.methods.stake({tier500:{}})
-      .accounts({
-        pool: pool.publicKey,
-        authority: user1.publicKey,
-        from: ata,
-      }).signers([user1]).rpc())
-      .to.be.rejectedWith(/Tier already used/);
+    position = await positionAccount(program, pool.publicKey, user1.publicKey, 0);
+    expect(position.status).to.have.property('ready');
   });
 
   it("Should NOT claim if StakeStatus == Ready", async () => {
     const ata = await getATA(user1.publicKey, mint.publicKey);
 
-    const staking = await stakingAccount(program, pool.publicKey, user1.publicKey);
-    expect(staking.stakes[0]).to.have.property('ready')
+    const position = await positionAccount(program, pool.publicKey, user1.publicKey, 0);
+    expect(position.status).to.have.property('ready')
 
     await expect(program.methods.claim()
       .accounts({
         pool: pool.publicKey,
         authority: user1.publicKey,
         to: ata,
-      }).signers([user1]).rpc())
+      })
+      .remainingAccounts(await positionMetas(program, user1.publicKey, [await positionAddress(program, pool.publicKey, user1.publicKey, 0)]))
+      .signers([user1]).rpc())
       .to.be.rejectedWith(/The user doesn't have any stakes/);
   });
 
@@ -317,38 +324,63 @@ describe("basic staking", () => {
 
     await pause(program, pool.publicKey, authority);
 
-    await expect(program.methods.claim()
+    await expect(program.methods.unstake()
       .accounts({
         pool: pool.publicKey,
         authority: user1.publicKey,
+        position: await positionAddress(program, pool.publicKey, user1.publicKey, 0),
         to: ata,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user1.publicKey, 0), user1.publicKey),
       }).signers([user1]).rpc())
       .to.be.rejectedWith(/Pool is paused/);
 
     await unpause(program, pool.publicKey, authority);
   });
 
+  it("Should NOT unstake position of another user", async () => {
+    const ata = await mintToATA(spl_program, user3.publicKey, new BN(5_000_000), mint.publicKey, provider.wallet.publicKey);
+
+    await program.methods.createUser().accounts({
+      pool: pool.publicKey,
+      authority: user3.publicKey,
+    }).signers([user3]).rpc();
+
+    await expect(program.methods.unstake()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user3.publicKey,
+        position: await positionAddress(program, pool.publicKey, user1.publicKey, 0),
+        to: ata,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user1.publicKey, 0), user3.publicKey),
+      }).signers([user3]).rpc())
+      .to.be.rejected;
+  });
+
   it("Should unstake", async() => {
     const user1ata = await getATA(user1.publicKey, mint.publicKey);
 
     const userBalanceBefore = await tokenBalance(spl_program, user1ata);
 
-    let user1stakingAccount = await stakingAccount(program, pool.publicKey, user1.publicKey);
-    expect(user1stakingAccount.stakes[0]).to.be.deep.equal({ready:{}});
+    const position = await positionAccount(program, pool.publicKey, user1.publicKey, 0);
+    expect(position.status).to.have.property('ready');
 
     const vaultBalanceBefore = await vaultBalance(spl_program, pool.publicKey, program.programId);
 
-    await program.methods.unstake({tier500:{}})
+    await program.methods.unstake()
       .accounts({
         pool: pool.publicKey,
         authority: user1.publicKey,
+        position: await positionAddress(program, pool.publicKey, user1.publicKey, 0),
         to: user1ata,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user1.publicKey, 0), user1.publicKey),
       })
       .signers([user1])
       .rpc({commitment:'confirmed'});
 
-    user1stakingAccount = await stakingAccount(program, pool.publicKey, user1.publicKey);
-    expect(user1stakingAccount.stakes[0]).to.be.deep.equal({used:{}});
+    expect(await positionAccount(program, pool.publicKey, user1.publicKey, 0)).to.be.null;
+
+    const user1staking = await stakingAccount(program, pool.publicKey, user1.publicKey);
+    expect(user1staking.positions[0]).to.be.equal(0);
 
     const vaultBalanceAfter = await vaultBalance(spl_program, pool.publicKey, program.programId);
     expect(vaultBalanceBefore - vaultBalanceAfter).to.be.equal(5_000_000);
@@ -357,78 +389,81 @@ describe("basic staking", () => {
     expect(userBalanceAfter - userBalanceBefore).to.be.equal(5_000_000);
   });
 
-  it("Should NOT claim if StakeStatus == Used", async () => {
+  it("Should NOT claim if position is closed", async () => {
     const ata = await getATA(user1.publicKey, mint.publicKey);
 
-    const staking = await stakingAccount(program, pool.publicKey, user1.publicKey);
-    expect(staking.stakes[0]).to.have.property('used')
-
     await expect(program.methods.claim()
       .accounts({
         pool: pool.publicKey,
         authority: user1.publicKey,
         to: ata,
-      }).signers([user1]).rpc())
-      .to.be.rejectedWith(/The user doesn't have any stakes/);
+      })
+      .remainingAccounts(await positionMetas(program, user1.publicKey, [await positionAddress(program, pool.publicKey, user1.publicKey, 0)]))
+      .signers([user1]).rpc())
+      .to.be.rejected;
   });
 
-  it ("Should NOT unstake if used", async() => {
+  it ("Should NOT unstake if position is closed", async() => {
     const ata = await getATA(user1.publicKey, mint.publicKey);
 
-    const staking = await stakingAccount(program, pool.publicKey, user1.publicKey);
-    expect(staking.stakes[0]).to.have.property('used');
-
     await expect(
-      program.methods.unstake({tier500:{}})
+      program.methods.unstake()
         .accounts({
           pool: pool.publicKey,
           authority: user1.publicKey,
+          position: await positionAddress(program, pool.publicKey, user1.publicKey, 0),
           to: ata,
+          ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user1.publicKey, 0), user1.publicKey),
         })
         .signers([user1])
         .rpc()
-    ).to.be.rejectedWith(/The user doesn't have stake in this tier/);
+    ).to.be.rejected;
   });
 
-  it("Should NOT stake if already staked (StakeStatus == Used)", async() => {
+  it("Should re-stake into the same tier after unstake", async() => {
     const ata = await getATA(user1.publicKey, mint.publicKey);
 
-    const staking = await stakingAccount(program, pool.publicKey, user1.publicKey);
-    expect(staking.stakes[0]).to.have.property('used');
-
-
-    await expect(program.methods.stake({tier500:{}})
+    await program.methods.stake(0, new BN(5_000_000))
       .accounts({
         pool: pool.publicKey,
         authority: user1.publicKey,
+        position: await positionAddress(program, pool.publicKey, user1.publicKey, 1),
         from: ata,
-      }).signers([user1]).rpc())
-      .to.be.rejectedWith(/Tier already used/);
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user1.publicKey, 1), user1.publicKey),
+      }).signers([user1]).rpc();
+
+    const position = await positionAccount(program, pool.publicKey, user1.publicKey, 1);
+    expect(position.index.toNumber()).to.be.equal(1);
+    expect(position.status).to.have.property('staking');
+
+    const user1staking = await stakingAccount(program, pool.publicKey, user1.publicKey);
+    expect(user1staking.nextPosition.toNumber()).to.be.equal(2);
+    expect(user1staking.positions[0]).to.be.equal(1);
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.tiers[0].slots).to.be.equal(1);
+    expect(poolAccount.tiers[0].completed).to.be.equal(1);
   });
 
-  it("Should fetch user with stakes", async () => {
-    // List of authority of user accounts with stakes - Staking { .. } , Ready or Used
+  it("Should fetch positions in tier", async () => {
+    // List of positions in tier[0] of this pool - Staking { .. } or Ready
 
-    // tier[0] offset
+    // Position: discriminator, pool, user, index, tier
     const tierIdx = 0;
-    const tierOffset = 8 + 32 + 32 + tierIdx * (1 + 24);
-    // 0 - None
-    // 1 - Staking
-    // 2 - Ready
-    // 3 - Used
-    const stakeStatusBytes = anchor.utils.bytes.bs58.encode(Buffer.from([3]));
-
-    const list = await program.account.user.all([
+    const tierOffset = 8 + 32 + 32 + 8;
+    const tierBytes = anchor.utils.bytes.bs58.encode(Buffer.from([tierIdx]));
+
+    const list = await program.account.position.all([
       {
-        memcmp: { // Filter users of this pool
+        memcmp: { // Filter positions of this pool
           offset: 8,
           bytes: pool.publicKey.toBase58(),
         }
       },
       {
-        memcmp: { // Filter users with Used stakes in tier[0]
+        memcmp: { // Filter positions in tier[0]
           offset: tierOffset,
-          bytes: stakeStatusBytes,
+          bytes: tierBytes,
         }
       },
     ])
@@ -441,11 +476,13 @@ describe("basic staking", () => {
     await pause(program, pool.publicKey, authority);
 
     await expect(
-      program.methods.stake({tier1000:{}})
+      program.methods.stake(1, new BN(10_000_000))
         .accounts({
           pool: pool.publicKey,
           authority: user1.publicKey,
+          position: await positionAddress(program, pool.publicKey, user1.publicKey, 2),
           from: ata,
+          ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user1.publicKey, 2), user1.publicKey),
         }).signers([user1]).rpc()
     ).to.be.rejectedWith(/Pool is paused/)
 
@@ -458,11 +495,13 @@ describe("basic staking", () => {
     await close(program, pool.publicKey, authority);
 
     await expect(
-      program.methods.stake({tier1000:{}})
+      program.methods.stake(1, new BN(10_000_000))
         .accounts({
           pool: pool.publicKey,
           authority: user1.publicKey,
+          position: await positionAddress(program, pool.publicKey, user1.publicKey, 2),
           from: ata,
+          ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user1.publicKey, 2), user1.publicKey),
         }).signers([user1]).rpc()
     ).to.be.rejectedWith(/Pool is closed for new staking/)
 
@@ -474,15 +513,17 @@ describe("basic staking", () => {
     await createToken(spl_program, token, mint.publicKey, user1.publicKey);
     expect(await tokenBalance(spl_program, token.publicKey)).to.be.equal(0);
 
-    await expect(program.methods.stake({tier1000:{}})
+    await expect(program.methods.stake(1, new BN(10_000_000))
         .accounts({
           pool: pool.publicKey,
           authority: user1.publicKey,
+          position: await positionAddress(program, pool.publicKey, user1.publicKey, 2),
           from: token.publicKey,
+          ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user1.publicKey, 2), user1.publicKey),
         }).signers([user1]).rpc()).to.be.rejected;
   });
 
-  it("Should NOT claim if StakeStatus == None", async () => {
+  it("Should NOT claim without positions", async () => {
     const user = user2;
     const ata = await mintToATA(spl_program, user.publicKey, new BN(5_000_000), mint.publicKey, provider.wallet.publicKey);
 
@@ -499,16 +540,31 @@ describe("basic staking", () => {
     ).to.be.rejectedWith(/The user doesn't have any stakes/)
   });
 
-  it("Should NOT unstake if StakeStatus == None", async() => {
+  it("Should NOT claim position of another user", async () => {
     const user = user2;
-    const ata = await mintToATA(spl_program, user.publicKey, new BN(5_000_000), mint.publicKey, provider.wallet.publicKey);
+    const ata = await getATA(user.publicKey, mint.publicKey);
 
-    await expect(program.methods.unstake({tier500:{}}).accounts({
+    await expect(program.methods.claim().accounts({
         pool: pool.publicKey,
         authority: user.publicKey,
         to: ata,
-      }).signers([user]).rpc()
-    ).to.be.rejectedWith(/The user doesn't have stake in this tier./)
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [await positionAddress(program, pool.publicKey, user1.publicKey, 1)]))
+      .signers([user]).rpc()
+    ).to.be.rejectedWith(/Invalid position/)
+  });
+
+  it("Should NOT stake into non-existent tier", async() => {
+    const ata = await getATA(user2.publicKey, mint.publicKey);
+
+    await expect(program.methods.stake(3, new BN(5_000_000)).accounts({
+        pool: pool.publicKey,
+        authority: user2.publicKey,
+        position: await positionAddress(program, pool.publicKey, user2.publicKey, 0),
+        from: ata,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user2.publicKey, 0), user2.publicKey),
+      }).signers([user2]).rpc()
+    ).to.be.rejectedWith(/Invalid tier/)
   });
 
   //it("Should", async () => {});
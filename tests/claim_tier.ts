@@ -0,0 +1,196 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  createToken,
+  mintTo,
+  mintToATA, tokenBalance, positionAddress, receiptAccounts, positionAccount, positionMetas, waitUntilblockTime, blockTimeFromTx, getATA
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("claim tier", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  const user = Keypair.generate();
+
+  before(async () => {
+    const funderToken = Keypair.generate();
+
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+    await createToken(spl_program, funderToken, mint.publicKey, provider.wallet.publicKey);
+    await mintTo(spl_program, 1_000_000_000, mint.publicKey, funderToken.publicKey, provider.wallet.publicKey);
+
+    const tiers = [
+      {
+        supply: 5,
+        slots: 5,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(600_000_000),
+        curve: { linear: {} },
+      },
+      {
+        supply: 5,
+        slots: 5,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(1000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await spl_program.methods.transfer(new BN(100_000_000))
+      .accounts({
+        source: funderToken.publicKey,
+        destination: rewardVault,
+        authority: provider.wallet.publicKey,
+      })
+      .postInstructions(
+        [
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: user.publicKey
+          }),
+        ]
+      )
+      .rpc();
+
+    await mintToATA(spl_program, user.publicKey, new BN(10_000_000), mint.publicKey, provider.wallet.publicKey);
+  });
+
+  it("Should claim reward of a single tier", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+
+    const position0 = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+    const position1 = await positionAddress(program, pool.publicKey, user.publicKey, 1);
+
+    const tx = await program.methods.stake(0, new BN(5_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position: position0,
+        from: ata,
+        ...await receiptAccounts(program, position0, user.publicKey),
+      })
+      .preInstructions(
+        [
+          await program.methods.createUser()
+            .accounts({
+              pool: pool.publicKey,
+              authority: user.publicKey,
+            }).instruction()
+        ]
+      )
+      .postInstructions(
+        [
+          await program.methods.stake(1, new BN(5_000_000))
+            .accounts({
+              pool: pool.publicKey,
+              authority: user.publicKey,
+              position: position1,
+              from: ata,
+              ...await receiptAccounts(program, position1, user.publicKey),
+            }).instruction()
+        ]
+      )
+      .signers([user])
+      .rpc({commitment:'confirmed'});
+
+    const start = await blockTimeFromTx(provider, tx);
+    await waitUntilblockTime(provider, start + 4);
+
+    const ataBefore = await tokenBalance(spl_program, ata);
+    await program.methods.claimTier(0)
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        to: ata,
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [position0, position1]))
+      .signers([user])
+      .rpc();
+
+    expect(await tokenBalance(spl_program, ata) - ataBefore).to.be.equal(3_000_000);
+
+    const claimed = await positionAccount(program, pool.publicKey, user.publicKey, 0);
+    expect(claimed.status).to.have.property('ready');
+
+    const skipped = await positionAccount(program, pool.publicKey, user.publicKey, 1);
+    expect(skipped.status.staking.rewardPaid.toNumber()).to.be.equal(0);
+    expect(skipped.status.staking.lastClaimed.toNumber()).to.be.equal(start);
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.metrics.rewardPaid.toNumber()).to.be.equal(3_000_000);
+  });
+
+  it("Should NOT claim tier without staking positions", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+
+    await expect(program.methods.claimTier(0)
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        to: ata,
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [
+        await positionAddress(program, pool.publicKey, user.publicKey, 0),
+        await positionAddress(program, pool.publicKey, user.publicKey, 1),
+      ]))
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/The user doesn't have any stakes/);
+  });
+
+  it("Should NOT claim invalid tier", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+
+    await expect(program.methods.claimTier(2)
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        to: ata,
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [
+        await positionAddress(program, pool.publicKey, user.publicKey, 1),
+      ]))
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/Invalid tier/);
+  });
+});
@@ -0,0 +1,260 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  createToken,
+  mintTo,
+  mintToATA, tokenBalance, stakingAccount, positionAddress, receiptAccounts, positionMetas, waitUntilblockTime, getATA
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("claim delegate", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  const user = Keypair.generate();
+  const delegate = Keypair.generate();
+  const destination = Keypair.generate();
+
+  before(async () => {
+    const funderToken = Keypair.generate();
+
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+    await createToken(spl_program, funderToken, mint.publicKey, provider.wallet.publicKey);
+    await mintTo(spl_program, 1_000_000_000, mint.publicKey, funderToken.publicKey, provider.wallet.publicKey);
+
+    const tiers = [
+      {
+        supply: 5,
+        slots: 5,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await spl_program.methods.transfer(new BN(100_000_000))
+      .accounts({
+        source: funderToken.publicKey,
+        destination: rewardVault,
+        authority: provider.wallet.publicKey,
+      })
+      .postInstructions(
+        [
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: user.publicKey
+          }),
+        ]
+      )
+      .rpc();
+
+    await mintToATA(spl_program, user.publicKey, new BN(5_000_000), mint.publicKey, provider.wallet.publicKey);
+    await mintToATA(spl_program, destination.publicKey, new BN(0), mint.publicKey, provider.wallet.publicKey);
+  });
+
+  async function userAddress() {
+    const [address, _nonce] = await PublicKey.findProgramAddress(
+      [pool.publicKey.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    return address;
+  }
+
+  it("Should set delegate and reward destination", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+    const rewardDestination = await getATA(destination.publicKey, mint.publicKey);
+
+    await program.methods.stake(0, new BN(5_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position: await positionAddress(program, pool.publicKey, user.publicKey, 0),
+        from: ata,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user.publicKey, 0), user.publicKey),
+      })
+      .preInstructions(
+        [
+          await program.methods.createUser()
+            .accounts({
+              pool: pool.publicKey,
+              authority: user.publicKey,
+            }).instruction()
+        ]
+      )
+      .postInstructions(
+        [
+          await program.methods.setDelegate(delegate.publicKey, rewardDestination)
+            .accounts({
+              pool: pool.publicKey,
+              authority: user.publicKey,
+            }).instruction()
+        ]
+      )
+      .signers([user])
+      .rpc();
+
+    const staking = await stakingAccount(program, pool.publicKey, user.publicKey);
+    expect(staking.delegate).to.be.deep.equal(delegate.publicKey);
+    expect(staking.rewardDestination).to.be.deep.equal(rewardDestination);
+  });
+
+  it("Should NOT claim to other than reward destination", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+
+    await expect(program.methods.claim()
+      .accounts({
+        pool: pool.publicKey,
+        user: await userAddress(),
+        authority: user.publicKey,
+        to: ata,
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [
+        await positionAddress(program, pool.publicKey, user.publicKey, 0),
+      ]))
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/Reward must be sent to the user reward destination/);
+  });
+
+  it("Should NOT claim by stranger", async () => {
+    const stranger = Keypair.generate();
+
+    await expect(program.methods.claim()
+      .accounts({
+        pool: pool.publicKey,
+        user: await userAddress(),
+        authority: stranger.publicKey,
+        to: await getATA(destination.publicKey, mint.publicKey),
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [
+        await positionAddress(program, pool.publicKey, user.publicKey, 0),
+      ]))
+      .signers([stranger])
+      .rpc()).to.be.rejectedWith(/Signer is not the receipt holder or user delegate/);
+  });
+
+  it("Should NOT claim by delegate without reward destination", async () => {
+    const rewardDestination = await getATA(destination.publicKey, mint.publicKey);
+    const delegateAta = await mintToATA(spl_program, delegate.publicKey, new BN(0), mint.publicKey, provider.wallet.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+
+    await program.methods.setDelegate(delegate.publicKey, null)
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+      })
+      .signers([user])
+      .rpc();
+
+    const staking = await program.account.position.fetch(position);
+    await waitUntilblockTime(provider, staking.status.staking.lockedUntil.toNumber() + 1);
+
+    await expect(program.methods.claim()
+      .accounts({
+        pool: pool.publicKey,
+        user: await userAddress(),
+        authority: delegate.publicKey,
+        to: delegateAta,
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [position]))
+      .signers([delegate])
+      .rpc()).to.be.rejectedWith(/Reward must be sent to the user reward destination/);
+
+    await program.methods.setDelegate(delegate.publicKey, rewardDestination)
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+      })
+      .signers([user])
+      .rpc();
+  });
+
+  it("Should claim by delegate to reward destination", async () => {
+    const rewardDestination = await getATA(destination.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+
+    const staking = await program.account.position.fetch(position);
+    await waitUntilblockTime(provider, staking.status.staking.lockedUntil.toNumber() + 1);
+
+    await program.methods.claim()
+      .accounts({
+        pool: pool.publicKey,
+        user: await userAddress(),
+        authority: delegate.publicKey,
+        to: rewardDestination,
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [position]))
+      .signers([delegate])
+      .rpc();
+
+    expect(await tokenBalance(spl_program, rewardDestination)).to.be.equal(5_000_000);
+  });
+
+  it("Should NOT unstake by delegate", async () => {
+    await expect(program.methods.unstake()
+      .accounts({
+        pool: pool.publicKey,
+        user: await userAddress(),
+        authority: delegate.publicKey,
+        position: await positionAddress(program, pool.publicKey, user.publicKey, 0),
+        to: await getATA(destination.publicKey, mint.publicKey),
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user.publicKey, 0), delegate.publicKey),
+      })
+      .signers([delegate])
+      .rpc()).to.be.rejected;
+  });
+
+  it("Should unstake principal by authority", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+
+    await program.methods.unstake()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position: await positionAddress(program, pool.publicKey, user.publicKey, 0),
+        to: ata,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user.publicKey, 0), user.publicKey),
+      })
+      .signers([user])
+      .rpc();
+
+    expect(await tokenBalance(spl_program, ata)).to.be.equal(5_000_000);
+  });
+});
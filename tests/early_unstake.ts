@@ -0,0 +1,196 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  createToken,
+  mintTo,
+  mintToATA, tokenBalance, positionAddress, receiptAccounts, waitUntilblockTime, blockTimeFromTx, vaultBalance, rewardBalance, getATA
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("early unstake", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  const user = Keypair.generate();
+
+  before(async () => {
+    const funderToken = Keypair.generate();
+
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+    await createToken(spl_program, funderToken, mint.publicKey, provider.wallet.publicKey);
+    await mintTo(spl_program, 1_000_000_000, mint.publicKey, funderToken.publicKey, provider.wallet.publicKey);
+
+    const tiers = [
+      {
+        supply: 3,
+        slots: 3,
+        minStake: new BN(10_000_000),
+        maxStake: new BN(10_000_000),
+        duration: new BN(1000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
+        penalty: 10,
+        forfeitReward: true,
+      },
+      {
+        supply: 3,
+        slots: 3,
+        minStake: new BN(10_000_000),
+        maxStake: new BN(10_000_000),
+        duration: new BN(5),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
+        penalty: 10,
+        forfeitReward: false,
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await spl_program.methods.transfer(new BN(100_000_000))
+      .accounts({
+        source: funderToken.publicKey,
+        destination: rewardVault,
+        authority: provider.wallet.publicKey,
+      })
+      .postInstructions(
+        [
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: user.publicKey
+          }),
+        ]
+      )
+      .rpc();
+
+    await mintToATA(spl_program, user.publicKey, new BN(20_000_000), mint.publicKey, provider.wallet.publicKey);
+  });
+
+  it("Should early unstake with penalty and forfeit reward", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+
+    await program.methods.stake(0, new BN(10_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .preInstructions(
+        [
+          await program.methods.createUser()
+            .accounts({
+              pool: pool.publicKey,
+              authority: user.publicKey,
+            }).instruction()
+        ]
+      )
+      .signers([user])
+      .rpc();
+
+    const ataBefore = await tokenBalance(spl_program, ata);
+    const rewardBefore = await rewardBalance(spl_program, pool.publicKey, program.programId);
+
+    await program.methods.earlyUnstake()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        to: ata,
+        rewardTo: ata,
+        penaltyTo: rewardVault,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .signers([user])
+      .rpc();
+
+    expect(await tokenBalance(spl_program, ata) - ataBefore).to.be.equal(9_000_000);
+    expect(await rewardBalance(spl_program, pool.publicKey, program.programId) - rewardBefore).to.be.equal(1_000_000);
+    expect(await vaultBalance(spl_program, pool.publicKey, program.programId)).to.be.equal(0);
+
+    expect(await program.account.position.fetchNullable(position)).to.be.null;
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.metrics.rewardRequirements.toNumber()).to.be.equal(0);
+    expect(poolAccount.metrics.staked.toNumber()).to.be.equal(0);
+    expect(poolAccount.tiers[0].completed).to.be.equal(1);
+  });
+
+  it("Should NOT early unstake after time lock", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 1);
+
+    const tx = await program.methods.stake(1, new BN(10_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .signers([user])
+      .rpc({commitment: 'confirmed'});
+
+    const start = await blockTimeFromTx(provider, tx);
+    await waitUntilblockTime(provider, start + 6);
+
+    await expect(program.methods.earlyUnstake()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        to: ata,
+        rewardTo: ata,
+        penaltyTo: rewardVault,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/The time lock has passed/);
+  });
+});
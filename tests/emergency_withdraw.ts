@@ -0,0 +1,227 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  pause,
+  unpause,
+  creatMintIfRequired,
+  createToken,
+  mintTo,
+  mintToATA, tokenBalance, positionAddress, receiptAccounts, positionAccount, vaultBalance, getATA
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("emergency withdraw", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  const user = Keypair.generate();
+
+  before(async () => {
+    const funderToken = Keypair.generate();
+
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+    await createToken(spl_program, funderToken, mint.publicKey, provider.wallet.publicKey);
+    await mintTo(spl_program, 1_000_000_000, mint.publicKey, funderToken.publicKey, provider.wallet.publicKey);
+
+    const tiers = [
+      {
+        supply: 3,
+        slots: 3,
+        minStake: new BN(10_000_000),
+        maxStake: new BN(10_000_000),
+        duration: new BN(1000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await spl_program.methods.transfer(new BN(10_000_000))
+      .accounts({
+        source: funderToken.publicKey,
+        destination: rewardVault,
+        authority: provider.wallet.publicKey,
+      })
+      .postInstructions(
+        [
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: user.publicKey
+          }),
+        ]
+      )
+      .rpc();
+
+    await mintToATA(spl_program, user.publicKey, new BN(10_000_000), mint.publicKey, provider.wallet.publicKey);
+
+    const ata = await getATA(user.publicKey, mint.publicKey);
+    await program.methods.stake(0, new BN(10_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position: await positionAddress(program, pool.publicKey, user.publicKey, 0),
+        from: ata,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user.publicKey, 0), user.publicKey),
+      })
+      .preInstructions(
+        [
+          await program.methods.createUser()
+            .accounts({
+              pool: pool.publicKey,
+              authority: user.publicKey,
+            }).instruction()
+        ]
+      )
+      .signers([user])
+      .rpc();
+  });
+
+  it("Should NOT emergency withdraw if not enabled", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+
+    await expect(program.methods.emergencyWithdraw()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position: await positionAddress(program, pool.publicKey, user.publicKey, 0),
+        to: ata,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user.publicKey, 0), user.publicKey),
+      })
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/Emergency withdraw is not enabled/);
+  });
+
+  it("Should NOT enable emergency by non-authority", async () => {
+    await expect(program.methods.enableEmergency()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+      })
+      .signers([user])
+      .rpc()).to.be.rejected;
+  });
+
+  it("Should emergency withdraw principal while paused", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+
+    await pause(program, pool.publicKey, authority);
+    await program.methods.enableEmergency()
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    let poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.emergency).to.be.true;
+    expect(poolAccount.closed).to.be.true;
+
+    await program.methods.emergencyWithdraw()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position: await positionAddress(program, pool.publicKey, user.publicKey, 0),
+        to: ata,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user.publicKey, 0), user.publicKey),
+      })
+      .signers([user])
+      .rpc();
+
+    expect(await tokenBalance(spl_program, ata)).to.be.equal(10_000_000);
+    expect(await vaultBalance(spl_program, pool.publicKey, program.programId)).to.be.equal(0);
+
+    expect(await positionAccount(program, pool.publicKey, user.publicKey, 0)).to.be.null;
+
+    poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.tiers[0].completed).to.be.equal(1);
+    expect(poolAccount.metrics.staked.toNumber()).to.be.equal(0);
+    expect(poolAccount.metrics.rewardRequirements.toNumber()).to.be.equal(0);
+
+    await expect(program.methods.emergencyWithdraw()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position: await positionAddress(program, pool.publicKey, user.publicKey, 0),
+        to: ata,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user.publicKey, 0), user.publicKey),
+      })
+      .signers([user])
+      .rpc()).to.be.rejected;
+  });
+
+  it("Should free pool after emergency withdraw", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+
+    await unpause(program, pool.publicKey, authority);
+
+    const [userStaking, _nonce] = await PublicKey.findProgramAddress(
+      [pool.publicKey.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods.withdrawExtra()
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+        to: ata,
+        rewardTo: ata,
+      })
+      .postInstructions(
+        [
+          await program.methods.freeUser()
+            .accounts({
+              pool: pool.publicKey,
+              user: userStaking,
+              authority: authority.publicKey,
+              receiver: provider.wallet.publicKey,
+            }).instruction(),
+          await program.methods.freePool()
+            .accounts({
+              pool: pool.publicKey,
+              authority: authority.publicKey,
+              receiver: provider.wallet.publicKey,
+            }).instruction(),
+        ]
+      )
+      .signers([authority])
+      .rpc();
+
+    const poolAccount = await program.account.pool.fetchNullable(pool.publicKey);
+    expect(poolAccount).to.be.null;
+  });
+});
@@ -0,0 +1,268 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  mintTo,
+  mintToATA, pause, unpause, close, open
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("admin and lifecycle events", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  const user = Keypair.generate();
+
+  async function events(tx: string) {
+    const transaction = await provider.connection.getTransaction(tx, {commitment: "confirmed"});
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    const parsed = [];
+    parser.parseLogs(transaction.meta.logMessages, event => parsed.push(event));
+    return parsed;
+  }
+
+  before(async () => {
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+  });
+
+  it("Should emit initialize event with tier config", async () => {
+    const tiers = [
+      {
+        supply: 3,
+        slots: 3,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(600_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    const tx = await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const [event] = await events(tx);
+    expect(event.name).to.be.equal("InitializeEvent");
+    expect(event.data.pool).to.be.deep.equal(pool.publicKey);
+    expect(event.data.authority).to.be.deep.equal(authority.publicKey);
+    expect(event.data.tiers.length).to.be.equal(1);
+    expect(event.data.tiers[0].supply).to.be.equal(3);
+    expect(event.data.tiers[0].rewardRate.toNumber()).to.be.equal(600_000_000);
+  });
+
+  it("Should emit create user event", async () => {
+    const tx = await program.methods.createUser()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+      })
+      .preInstructions(
+        [
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: user.publicKey
+          }),
+        ]
+      )
+      .signers([user])
+      .rpc();
+
+    const [event] = await events(tx);
+    expect(event.name).to.be.equal("CreateUserEvent");
+    expect(event.data.authority).to.be.deep.equal(user.publicKey);
+    expect(event.data.allowance).to.be.equal(0);
+  });
+
+  it("Should emit pause, unpause, close and open events", async () => {
+    const names = [];
+    for (const action of [pause, unpause, close, open]) {
+      const [event] = await events(await action(program, pool.publicKey, authority));
+      expect(event.data.authority).to.be.deep.equal(authority.publicKey);
+      names.push(event.name);
+    }
+
+    expect(names).to.be.deep.equal(["PauseEvent", "UnpauseEvent", "CloseEvent", "OpenEvent"]);
+  });
+
+  it("Should emit pool settings events", async () => {
+    const treasury = await mintToATA(spl_program, authority.publicKey, new BN(0), mint.publicKey, provider.wallet.publicKey);
+    const merkleRoot = Array.from({length: 32}, (_, i) => i);
+
+    const setting = (method) => method.accounts({
+      pool: pool.publicKey,
+      authority: authority.publicKey,
+    }).instruction();
+
+    const tx = await program.methods.setTreasury()
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+        treasury,
+      })
+      .postInstructions(
+        [
+          await setting(program.methods.setStakingWindow(new BN(1), new BN(4_000_000_000))),
+          await setting(program.methods.setMerkleRoot(merkleRoot)),
+          await setting(program.methods.setMerkleRoot(null)),
+          await setting(program.methods.setSolvencyCheck(true)),
+          await setting(program.methods.setFundingCap(true)),
+          await setting(program.methods.setWithdrawDelay(new BN(0))),
+        ]
+      )
+      .signers([authority])
+      .rpc();
+
+    const [
+      setTreasury, setStakingWindow, setMerkleRoot, unsetMerkleRoot, setSolvencyCheck, setFundingCap, setWithdrawDelay
+    ] = await events(tx);
+    expect(setTreasury.name).to.be.equal("SetTreasuryEvent");
+    expect(setTreasury.data.treasury).to.be.deep.equal(treasury);
+
+    expect(setStakingWindow.name).to.be.equal("SetStakingWindowEvent");
+    expect(setStakingWindow.data.opensAt.toNumber()).to.be.equal(1);
+    expect(setStakingWindow.data.closesAt.toNumber()).to.be.equal(4_000_000_000);
+
+    expect(setMerkleRoot.name).to.be.equal("SetMerkleRootEvent");
+    expect(setMerkleRoot.data.merkleRoot).to.be.deep.equal(merkleRoot);
+    expect(unsetMerkleRoot.data.merkleRoot).to.be.null;
+
+    expect(setSolvencyCheck.name).to.be.equal("SetSolvencyCheckEvent");
+    expect(setSolvencyCheck.data.enabled).to.be.true;
+
+    expect(setFundingCap.name).to.be.equal("SetFundingCapEvent");
+    expect(setFundingCap.data.enabled).to.be.true;
+
+    expect(setWithdrawDelay.name).to.be.equal("SetWithdrawDelayEvent");
+    expect(setWithdrawDelay.data.withdrawDelay.toNumber()).to.be.equal(0);
+  });
+
+  it("Should emit set delegate event", async () => {
+    const delegate = Keypair.generate();
+
+    const tx = await program.methods.setDelegate(delegate.publicKey, null)
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+      })
+      .signers([user])
+      .rpc();
+
+    const [event] = await events(tx);
+    expect(event.name).to.be.equal("SetDelegateEvent");
+    expect(event.data.delegate).to.be.deep.equal(delegate.publicKey);
+    expect(event.data.rewardDestination).to.be.null;
+  });
+
+  it("Should emit withdraw extra, free user and free pool events", async () => {
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+    await mintTo(spl_program, 1_000_000, mint.publicKey, rewardVault, provider.wallet.publicKey);
+
+    const ata = await mintToATA(spl_program, authority.publicKey, new BN(0), mint.publicKey, provider.wallet.publicKey);
+    const [userStaking, _nonce2] = await PublicKey.findProgramAddress(
+      [pool.publicKey.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await close(program, pool.publicKey, authority);
+
+    const tx = await program.methods.withdrawExtra()
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+        to: ata,
+        rewardTo: ata,
+      })
+      .postInstructions(
+        [
+          await program.methods.freeUser()
+            .accounts({
+              pool: pool.publicKey,
+              user: userStaking,
+              authority: authority.publicKey,
+              receiver: provider.wallet.publicKey,
+            }).instruction(),
+          await program.methods.freePool()
+            .accounts({
+              pool: pool.publicKey,
+              authority: authority.publicKey,
+              receiver: provider.wallet.publicKey,
+            }).instruction(),
+        ]
+      )
+      .signers([authority])
+      .rpc();
+
+    const [withdraw, freeUser, freePool] = await events(tx);
+    expect(withdraw.name).to.be.equal("WithdrawExtraEvent");
+    expect(withdraw.data.amount.toNumber()).to.be.equal(0);
+    expect(withdraw.data.rewardAmount.toNumber()).to.be.equal(1_000_000);
+    expect(withdraw.data.rewardTo).to.be.deep.equal(ata);
+
+    expect(freeUser.name).to.be.equal("FreeUserEvent");
+    expect(freeUser.data.user).to.be.deep.equal(userStaking);
+    expect(freeUser.data.authority).to.be.deep.equal(user.publicKey);
+
+    expect(freePool.name).to.be.equal("FreePoolEvent");
+    expect(freePool.data.receiver).to.be.deep.equal(provider.wallet.publicKey);
+  });
+
+  it("Should emit enable emergency event", async () => {
+    const emergencyPool = Keypair.generate();
+    const tiers = [
+      {
+        supply: 1,
+        slots: 1,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(600_000_000),
+        curve: { linear: {} },
+      },
+    ];
+    await initializeProgram(program, emergencyPool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const tx = await program.methods.enableEmergency()
+      .accounts({
+        pool: emergencyPool.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    const [event] = await events(tx);
+    expect(event.name).to.be.equal("EnableEmergencyEvent");
+    expect(event.data.pool).to.be.deep.equal(emergencyPool.publicKey);
+    expect(event.data.authority).to.be.deep.equal(authority.publicKey);
+  });
+});
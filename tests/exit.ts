@@ -0,0 +1,155 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  createToken,
+  mintTo,
+  mintToATA, tokenBalance, vaultBalance, positionAddress, receiptAccounts, positionAccount, waitUntilblockTime, getATA
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("exit", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  const user = Keypair.generate();
+
+  before(async () => {
+    const funderToken = Keypair.generate();
+
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+    await createToken(spl_program, funderToken, mint.publicKey, provider.wallet.publicKey);
+    await mintTo(spl_program, 1_000_000_000, mint.publicKey, funderToken.publicKey, provider.wallet.publicKey);
+
+    const tiers = [
+      {
+        supply: 5,
+        slots: 5,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await spl_program.methods.transfer(new BN(100_000_000))
+      .accounts({
+        source: funderToken.publicKey,
+        destination: rewardVault,
+        authority: provider.wallet.publicKey,
+      })
+      .postInstructions(
+        [
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: user.publicKey
+          }),
+        ]
+      )
+      .rpc();
+
+    await mintToATA(spl_program, user.publicKey, new BN(5_000_000), mint.publicKey, provider.wallet.publicKey);
+  });
+
+  it("Should NOT exit before lock time", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+
+    await program.methods.stake(0, new BN(5_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .preInstructions(
+        [
+          await program.methods.createUser()
+            .accounts({
+              pool: pool.publicKey,
+              authority: user.publicKey,
+            }).instruction()
+        ]
+      )
+      .signers([user])
+      .rpc();
+
+    await expect(program.methods.exit()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        to: ata,
+        rewardTo: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/The time lock has not yet passed/);
+  });
+
+  it("Should exit with remaining reward and principal", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+
+    const staking = await program.account.position.fetch(position);
+    await waitUntilblockTime(provider, staking.status.staking.lockedUntil.toNumber() + 1);
+
+    await program.methods.exit()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        to: ata,
+        rewardTo: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .signers([user])
+      .rpc();
+
+    expect(await tokenBalance(spl_program, ata)).to.be.equal(10_000_000);
+    expect(await vaultBalance(spl_program, pool.publicKey, program.programId)).to.be.equal(0);
+    expect(await positionAccount(program, pool.publicKey, user.publicKey, 0)).to.be.null;
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.tiers[0].completed).to.be.equal(1);
+    expect(poolAccount.metrics.staked.toNumber()).to.be.equal(0);
+    expect(poolAccount.metrics.rewardPaid.toNumber()).to.be.equal(5_000_000);
+  });
+});
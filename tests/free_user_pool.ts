@@ -11,7 +11,7 @@ import {
   creatMintIfRequired,
   createToken,
   mintTo,
-  mintToATA, tokenBalance, stakingAccount, waitUntilblockTime, blockTimeFromTx, vaultBalance, rewardBalance, getATA
+  mintToATA, tokenBalance, stakingAccount, positionAddress, receiptAccounts, positionMetas, waitUntilblockTime, blockTimeFromTx, vaultBalance, rewardBalance, getATA
 } from './utils';
 
 import {expect, use} from 'chai';
@@ -67,23 +67,29 @@ describe("free user and pool", () => {
       {
         supply: 3,
         slots: 3,
-        stake: new BN(5_000_000),
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
         duration: new BN(2),
-        reward: new BN(5_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       },
       {
         supply: 3,
         slots: 3,
-        stake: new BN(10_000_000),
+        minStake: new BN(10_000_000),
+        maxStake: new BN(10_000_000),
         duration: new BN(3),
-        reward: new BN(10_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       },
       {
         supply: 3,
         slots: 3,
-        stake: new BN(15_000_000),
+        minStake: new BN(15_000_000),
+        maxStake: new BN(15_000_000),
         duration: new BN(15),
-        reward: new BN(15_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       },
     ];
 
@@ -135,7 +141,7 @@ describe("free user and pool", () => {
       }).signers([user1]).rpc({commitment:'confirmed'});
 
     let staking = await stakingAccount(program, pool.publicKey, user1.publicKey);
-    expect(staking.stakes[0]).to.have.property('none');
+    expect(staking.positions[0]).to.be.equal(0);
 
     await close(program, pool.publicKey, authority);
 
@@ -167,7 +173,7 @@ describe("free user and pool", () => {
       }).signers([user1]).rpc({commitment:'confirmed'});
 
     let staking = await stakingAccount(program, pool.publicKey, user1.publicKey);
-    expect(staking.stakes[0]).to.have.property('none');
+    expect(staking.positions[0]).to.be.equal(0);
 
     await close(program, pool.publicKey, authority);
     await pause(program, pool.publicKey, authority);
@@ -207,11 +213,15 @@ describe("free user and pool", () => {
   it("Should NOT free user with active stake or pending reward", async() => {
     const ata = await getATA(user1.publicKey, mint.publicKey);
 
-    const tx = await program.methods.stake({tier500:{}})
+    const position = await positionAddress(program, pool.publicKey, user1.publicKey, 0);
+
+    const tx = await program.methods.stake(0, new BN(5_000_000))
       .accounts({
         pool: pool.publicKey,
         authority: user1.publicKey,
+        position,
         from: ata,
+        ...await receiptAccounts(program, position, user1.publicKey),
       }).signers([user1]).rpc();
 
     await close(program, pool.publicKey, authority);
@@ -237,7 +247,9 @@ describe("free user and pool", () => {
         pool: pool.publicKey,
         authority: user1.publicKey,
         to: ata,
-      }).signers([user1]).rpc();
+      })
+      .remainingAccounts(await positionMetas(program, user1.publicKey, [position]))
+      .signers([user1]).rpc();
 
     await expect(program.methods.freeUser()
       .accounts({
@@ -247,11 +259,13 @@ describe("free user and pool", () => {
         receiver: provider.wallet.publicKey,
       }).signers([authority]).rpc()).to.be.rejectedWith(/UserHasActiveStakes/);
 
-    await program.methods.unstake({tier500:{}})
+    await program.methods.unstake()
       .accounts({
         pool: pool.publicKey,
         authority: user1.publicKey,
+        position,
         to: ata,
+        ...await receiptAccounts(program, position, user1.publicKey),
       }).signers([user1]).rpc();
 
     await program.methods.freeUser()
@@ -297,6 +311,7 @@ describe("free user and pool", () => {
 
   it("Should NOT free pool if any active stakes", async () => {
     const ata = await getATA(user1.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user1.publicKey, 0);
 
     const tx = await program.methods.createUser()
       .accounts({
@@ -304,11 +319,13 @@ describe("free user and pool", () => {
         authority: user1.publicKey,
       })
       .postInstructions([
-        await program.methods.stake({tier500:{}})
+        await program.methods.stake(0, new BN(5_000_000))
           .accounts({
             pool: pool.publicKey,
             authority: user1.publicKey,
+            position,
             from: ata,
+            ...await receiptAccounts(program, position, user1.publicKey),
           }).instruction()
       ])
       .signers([user1]).rpc();
@@ -332,7 +349,9 @@ describe("free user and pool", () => {
         pool: pool.publicKey,
         authority: user1.publicKey,
         to: ata,
-      }).signers([user1]).rpc();
+      })
+      .remainingAccounts(await positionMetas(program, user1.publicKey, [position]))
+      .signers([user1]).rpc();
 
     await expect(
       program.methods.freePool()
@@ -348,11 +367,13 @@ describe("free user and pool", () => {
       program.programId
     );
 
-    await program.methods.unstake({tier500:{}})
+    await program.methods.unstake()
       .accounts({
         pool: pool.publicKey,
         authority: user1.publicKey,
+        position,
         to: ata,
+        ...await receiptAccounts(program, position, user1.publicKey),
       }).postInstructions(
         [
           await program.methods.freeUser()
@@ -404,6 +425,7 @@ describe("free user and pool", () => {
         pool: pool.publicKey,
         authority: authority.publicKey,
         to: ata,
+        rewardTo: ata,
       }).signers([authority])
       .rpc({commitment:'confirmed'});
 
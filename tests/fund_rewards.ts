@@ -0,0 +1,112 @@
+import * as anchor from "@project-serum/anchor";
+import { Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  mintToATA, rewardBalance, tokenBalance, getATA
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("fund rewards", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  const funder = Keypair.generate();
+
+  before(async () => {
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+
+    // Pool can pay out at most 2 * 3_000_000
+    const tiers = [
+      {
+        supply: 2,
+        slots: 2,
+        minStake: new BN(1_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(600_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    await provider.sendAndConfirm(new web3.Transaction().add(
+      web3.SystemProgram.transfer({
+        fromPubkey: provider.wallet.publicKey,
+        lamports: web3.LAMPORTS_PER_SOL,
+        toPubkey: funder.publicKey
+      })
+    ));
+
+    await mintToATA(spl_program, funder.publicKey, new BN(10_000_000), mint.publicKey, provider.wallet.publicKey);
+  });
+
+  async function fund(amount: number) {
+    return program.methods.fundRewards(new BN(amount))
+      .accounts({
+        pool: pool.publicKey,
+        funder: funder.publicKey,
+        from: await getATA(funder.publicKey, mint.publicKey),
+      })
+      .signers([funder])
+      .rpc();
+  }
+
+  it("Should fund rewards by anyone", async () => {
+    await fund(4_000_000);
+
+    expect(await rewardBalance(spl_program, pool.publicKey, program.programId)).to.be.equal(4_000_000);
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.metrics.funded.toNumber()).to.be.equal(4_000_000);
+  });
+
+  it("Should NOT fund zero amount", async () => {
+    await expect(fund(0)).to.be.rejectedWith(/Amount must be greater than zero/);
+  });
+
+  it("Should NOT fund above total payable reward with funding cap", async () => {
+    await program.methods.setFundingCap(true)
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    await expect(fund(2_000_001)).to.be.rejectedWith(/Funding exceeds the total reward the pool can pay out/);
+
+    await fund(2_000_000);
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.metrics.funded.toNumber()).to.be.equal(6_000_000);
+    expect(await tokenBalance(spl_program, await getATA(funder.publicKey, mint.publicKey))).to.be.equal(4_000_000);
+  });
+});
@@ -53,23 +53,29 @@ describe("general", () => {
       {
         supply: 500,
         slots: 500,
-        stake: new BN(500_000_000),
+        minStake: new BN(500_000_000),
+        maxStake: new BN(500_000_000),
         duration: new BN(30*24*60*60),
-        reward: new BN(333_000_000),
+        rewardRate: new BN(666_000_000),
+        curve: { linear: {} },
       },
       {
         supply: 1000,
         slots: 1000,
-        stake: new BN(1000_000_000),
+        minStake: new BN(1000_000_000),
+        maxStake: new BN(1000_000_000),
         duration: new BN(60*24*60*60),
-        reward: new BN(1000_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       },
       {
         supply: 1500,
         slots: 1500,
-        stake: new BN(1500_000_000),
+        minStake: new BN(1500_000_000),
+        maxStake: new BN(1500_000_000),
         duration: new BN(90*24*60*60),
-        reward: new BN(1500_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       }
     ];
 
@@ -107,12 +113,116 @@ describe("general", () => {
     for (let idx = 0; idx < tiers.length; idx++) {
       expect(poolAccount.tiers[idx].supply).to.be.equal(tiers[idx].supply);
       expect(poolAccount.tiers[idx].slots).to.be.equal(tiers[idx].slots);
-      expect(poolAccount.tiers[idx].stake.eq(tiers[idx].stake)).to.be.true;
+      expect(poolAccount.tiers[idx].minStake.eq(tiers[idx].minStake)).to.be.true;
+      expect(poolAccount.tiers[idx].maxStake.eq(tiers[idx].maxStake)).to.be.true;
       expect(poolAccount.tiers[idx].duration.eq(tiers[idx].duration)).to.be.true;
-      expect(poolAccount.tiers[idx].reward.eq(tiers[idx].reward)).to.be.true;
+      expect(poolAccount.tiers[idx].rewardRate.eq(tiers[idx].rewardRate)).to.be.true;
     }
   });
 
+  it("Should NOT initialize with more than 8 tiers", async () => {
+    const tier = {
+      supply: 10,
+      slots: 10,
+      minStake: new BN(500_000_000),
+      maxStake: new BN(500_000_000),
+      duration: new BN(30*24*60*60),
+      rewardRate: new BN(666_000_000),
+      curve: { linear: {} },
+    };
+
+    await expect(initializeProgram(program, Keypair.generate(), authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, Array(9).fill(tier))).to.be.rejected;
+  });
+
+  it("Should update tier config", async () => {
+    await program.methods.updateTier(0, {
+      config: {
+        supply: 600,
+        minStake: new BN(100_000_000),
+        maxStake: new BN(600_000_000),
+        duration: new BN(30*24*60*60),
+        rewardRate: new BN(400_000_000),
+        curve: { cliff: { cliff: new BN(7*24*60*60) } },
+        penalty: 10,
+        forfeitReward: true,
+        maxPerWallet: 2,
+      }
+    })
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.tiers[0].supply).to.be.equal(600);
+    expect(poolAccount.tiers[0].slots).to.be.equal(600);
+    expect(poolAccount.tiers[0].minStake.toNumber()).to.be.equal(100_000_000);
+    expect(poolAccount.tiers[0].maxStake.toNumber()).to.be.equal(600_000_000);
+    expect(poolAccount.tiers[0].rewardRate.toNumber()).to.be.equal(400_000_000);
+    expect(poolAccount.tiers[0].penalty).to.be.equal(10);
+    expect(poolAccount.tiers[0].forfeitReward).to.be.true;
+    expect(poolAccount.tiers[0].maxPerWallet).to.be.equal(2);
+    expect(poolAccount.tiers[0].curve.cliff.cliff.toNumber()).to.be.equal(7*24*60*60);
+  });
+
+  it("Should add slots to tier", async () => {
+    await program.methods.updateTier(1, {addSlots: {slots: 100}})
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.tiers[1].supply).to.be.equal(1100);
+    expect(poolAccount.tiers[1].slots).to.be.equal(1100);
+  });
+
+  it("Should retire tier", async () => {
+    await program.methods.updateTier(1, {retire: {}})
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.tiers[1].supply).to.be.equal(0);
+    expect(poolAccount.tiers[1].slots).to.be.equal(0);
+
+    await program.methods.updateTier(1, {addSlots: {slots: 1000}})
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  it("Should NOT update tier with incorrect authority", async () => {
+    await expect(program.methods.updateTier(2, {retire: {}})
+      .accounts({
+        pool: pool.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc()).to.be.rejected;
+  });
+
+  it("Should NOT update non-existent tier", async () => {
+    await expect(program.methods.updateTier(3, {retire: {}})
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc()).to.be.rejectedWith(/Invalid tier/);
+  });
+
   it("Should pause staking", async () => {
     let poolAccount = await program.account.pool.fetch(pool.publicKey);
     expect(poolAccount.paused).to.be.false;
@@ -0,0 +1,255 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  createToken,
+  mintTo,
+  mintToATA, stakingAccount, positionAddress, receiptAccounts, getATA
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+import { createHash } from "crypto";
+
+chai.use(chaiAsPromised);
+
+function sha256(...data: Buffer[]) {
+  return createHash("sha256").update(Buffer.concat(data)).digest();
+}
+
+function leaf(wallet: PublicKey, allowance: number) {
+  const data = Buffer.alloc(2);
+  data.writeUInt16LE(allowance);
+  return sha256(wallet.toBuffer(), data);
+}
+
+function parent(a: Buffer, b: Buffer) {
+  return Buffer.compare(a, b) <= 0 ? sha256(a, b) : sha256(b, a);
+}
+
+describe("merkle allowlist", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  const user = Keypair.generate();
+  const other = Keypair.generate();
+  const stranger = Keypair.generate();
+
+  // Tree of three wallets: user limited to one position per tier, other and authority without limit
+  const leaves = [leaf(user.publicKey, 1), leaf(other.publicKey, 0), leaf(authority.publicKey, 0)];
+  const node = parent(leaves[0], leaves[1]);
+  const root = parent(node, leaves[2]);
+  const userProof = [leaves[1], leaves[2]].map(hash => Array.from(hash));
+  const otherProof = [leaves[0], leaves[2]].map(hash => Array.from(hash));
+
+  before(async () => {
+    const funderToken = Keypair.generate();
+
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+    await createToken(spl_program, funderToken, mint.publicKey, provider.wallet.publicKey);
+    await mintTo(spl_program, 1_000_000_000, mint.publicKey, funderToken.publicKey, provider.wallet.publicKey);
+
+    const tiers = [
+      {
+        supply: 5,
+        slots: 5,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(600_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await spl_program.methods.transfer(new BN(100_000_000))
+      .accounts({
+        source: funderToken.publicKey,
+        destination: rewardVault,
+        authority: provider.wallet.publicKey,
+      })
+      .postInstructions(
+        [
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: user.publicKey
+          }),
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: stranger.publicKey
+          }),
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: other.publicKey
+          }),
+        ]
+      )
+      .rpc();
+
+    await mintToATA(spl_program, user.publicKey, new BN(10_000_000), mint.publicKey, provider.wallet.publicKey);
+    await mintToATA(spl_program, other.publicKey, new BN(5_000_000), mint.publicKey, provider.wallet.publicKey);
+  });
+
+  async function setMerkleRoot(merkleRoot: number[] | null) {
+    return program.methods.setMerkleRoot(merkleRoot)
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  async function stake(index: number) {
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, index);
+
+    return program.methods.stake(0, new BN(5_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: await getATA(user.publicKey, mint.publicKey),
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  it("Should NOT set merkle root by non authority", async () => {
+    await expect(program.methods.setMerkleRoot(Array.from(root))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+      })
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/A has one constraint was violated/);
+  });
+
+  it("Should set merkle root", async () => {
+    await setMerkleRoot(Array.from(root));
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.merkleRoot).to.be.deep.equal(Array.from(root));
+  });
+
+  it("Should NOT create user without proof", async () => {
+    await expect(program.methods.createUser()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+      })
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/Allowlist proof is required/);
+  });
+
+  it("Should NOT create user with proof of another wallet", async () => {
+    await expect(program.methods.createUserWithProof(userProof, 1)
+      .accounts({
+        pool: pool.publicKey,
+        authority: stranger.publicKey,
+      })
+      .signers([stranger])
+      .rpc()).to.be.rejectedWith(/Wallet is not in the allowlist/);
+  });
+
+  it("Should NOT create user with wrong allowance", async () => {
+    await expect(program.methods.createUserWithProof(userProof, 2)
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+      })
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/Wallet is not in the allowlist/);
+  });
+
+  it("Should create user with proof and limit positions to allowance", async () => {
+    await program.methods.createUserWithProof(userProof, 1)
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+      })
+      .signers([user])
+      .rpc();
+
+    const staking = await stakingAccount(program, pool.publicKey, user.publicKey);
+    expect(staking.allowance).to.be.equal(1);
+
+    await stake(0);
+    await expect(stake(1)).to.be.rejectedWith(/Max positions per wallet reached for this tier/);
+  });
+
+  it("Should gate only new user creation when merkle root is rotated", async () => {
+    await program.methods.createUserWithProof(otherProof, 0)
+      .accounts({
+        pool: pool.publicKey,
+        authority: other.publicKey,
+      })
+      .signers([other])
+      .rpc();
+
+    // The new tree drops other, only new users are checked against it
+    await setMerkleRoot(Array.from(parent(leaves[0], leaves[2])));
+
+    const position = await positionAddress(program, pool.publicKey, other.publicKey, 0);
+    await program.methods.stake(0, new BN(5_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: other.publicKey,
+        position,
+        from: await getATA(other.publicKey, mint.publicKey),
+        ...await receiptAccounts(program, position, other.publicKey),
+      })
+      .signers([other])
+      .rpc();
+
+    const positionAccount = await program.account.position.fetch(position);
+    expect(positionAccount.status.staking.amount.toNumber()).to.be.equal(5_000_000);
+  });
+
+  it("Should allow anyone after merkle root is unset", async () => {
+    await setMerkleRoot(null);
+
+    await program.methods.createUser()
+      .accounts({
+        pool: pool.publicKey,
+        authority: stranger.publicKey,
+      })
+      .signers([stranger])
+      .rpc();
+
+    const staking = await stakingAccount(program, pool.publicKey, stranger.publicKey);
+    expect(staking.allowance).to.be.equal(0);
+  });
+});
@@ -11,7 +11,7 @@ import {
   creatMintIfRequired,
   createToken,
   mintTo,
-  mintToATA, tokenBalance, stakingAccount, waitUntilblockTime, blockTimeFromTx, vaultBalance, rewardBalance, getATA
+  mintToATA, tokenBalance, positionAddress, receiptAccounts, positionMetas, waitUntilblockTime, blockTimeFromTx, vaultBalance, rewardBalance, getATA
 } from './utils';
 
 import { expect } from 'chai';
@@ -55,23 +55,29 @@ describe("one user staking with prod tier settings", () => {
       {
         supply: 500,
         slots: 500,
-        stake: new BN(500_000_000),
+        minStake: new BN(500_000_000),
+        maxStake: new BN(500_000_000),
         duration: new BN(30*24*60*60),
-        reward: new BN(333_000_000),
+        rewardRate: new BN(666_000_000),
+        curve: { linear: {} },
       },
       {
         supply: 1000,
         slots: 1000,
-        stake: new BN(1000_000_000),
+        minStake: new BN(1000_000_000),
+        maxStake: new BN(1000_000_000),
         duration: new BN(60*24*60*60),
-        reward: new BN(1000_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       },
       {
         supply: 1500,
         slots: 1500,
-        stake: new BN(1500_000_000),
+        minStake: new BN(1500_000_000),
+        maxStake: new BN(1500_000_000),
         duration: new BN(90*24*60*60),
-        reward: new BN(1500_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       }
     ];
 
@@ -106,14 +112,18 @@ describe("one user staking with prod tier settings", () => {
   it("Should stake, claim, unstake", async () => {
     const ata = await getATA(user.publicKey, mint.publicKey);
 
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+
     const ataBefore = await tokenBalance(spl_program, ata);
 
     // Stake 5
-    let tx = await program.methods.stake({tier500: {}})
+    let tx = await program.methods.stake(0, new BN(500_000_000))
       .accounts({
         pool: pool.publicKey,
         authority: user.publicKey,
+        position,
         from: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
       })
       .preInstructions(
         [
@@ -131,9 +141,9 @@ describe("one user staking with prod tier settings", () => {
     expect(ataBefore - ataBegin).to.be.equal(500_000_000);
 
     const start = await blockTimeFromTx(provider, tx);
-    let staking = await stakingAccount(program, pool.publicKey, user.publicKey);
-    expect(staking.stakes[0]).to.have.property('staking');
-    expect(staking.stakes[0].staking.lastClaimed.toNumber()).to.be.equal(start);
+    let staking = await program.account.position.fetch(position);
+    expect(staking.status).to.have.property('staking');
+    expect(staking.status.staking.lastClaimed.toNumber()).to.be.equal(start);
 
     // Claim some amount
     await waitUntilblockTime(provider, start + 5);
@@ -142,12 +152,14 @@ describe("one user staking with prod tier settings", () => {
         pool: pool.publicKey,
         authority: user.publicKey,
         to: ata,
-      }).signers([user]).rpc({commitment: 'confirmed'});
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [position]))
+      .signers([user]).rpc({commitment: 'confirmed'});
 
     const middle = await blockTimeFromTx(provider, tx);
-    staking = await stakingAccount(program, pool.publicKey, user.publicKey);
-    expect(staking.stakes[0]).to.have.property('staking');
-    expect(staking.stakes[0].staking.lastClaimed.toNumber()).to.be.equal(middle);
+    staking = await program.account.position.fetch(position);
+    expect(staking.status).to.have.property('staking');
+    expect(staking.status.staking.lastClaimed.toNumber()).to.be.equal(middle);
 
     const ataMiddle = await tokenBalance(spl_program, ata);
 
@@ -11,7 +11,7 @@ import {
   creatMintIfRequired,
   createToken,
   mintTo,
-  mintToATA, tokenBalance, stakingAccount, waitUntilblockTime, blockTimeFromTx, vaultBalance, rewardBalance, getATA
+  mintToATA, tokenBalance, positionAddress, receiptAccounts, positionMetas, waitUntilblockTime, blockTimeFromTx, vaultBalance, rewardBalance, getATA
 } from './utils';
 
 import { expect } from 'chai';
@@ -57,23 +57,29 @@ describe("one user staking", () => {
       {
         supply: 2,
         slots: 2,
-        stake: new BN(5_000_000),
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
         duration: new BN(5),
-        reward: new BN(5_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       },
       {
         supply: 2,
         slots: 2,
-        stake: new BN(10_000_000),
+        minStake: new BN(10_000_000),
+        maxStake: new BN(10_000_000),
         duration: new BN(10),
-        reward: new BN(10_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       },
       {
         supply: 2,
         slots: 2,
-        stake: new BN(15_000_000),
+        minStake: new BN(15_000_000),
+        maxStake: new BN(15_000_000),
         duration: new BN(15),
-        reward: new BN(15_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       },
     ];
 
@@ -119,15 +125,18 @@ describe("one user staking", () => {
 
   it("Should stake, claim, unstake one tier", async () => {
     const ata = await getATA(user.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
 
     const ataBefore = await tokenBalance(spl_program, ata);
 
     // Stake 5
-    let tx = await program.methods.stake({tier500:{}})
+    let tx = await program.methods.stake(0, new BN(5_000_000))
       .accounts({
         pool: pool.publicKey,
         authority: user.publicKey,
+        position,
         from: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
       })
       .preInstructions(
         [
@@ -145,9 +154,9 @@ describe("one user staking", () => {
     expect(ataBefore - ataBegin).to.be.equal(5_000_000);
 
     const start = await blockTimeFromTx(provider, tx);
-    let staking = await stakingAccount(program, pool.publicKey, user.publicKey);
-    expect(staking.stakes[0]).to.have.property('staking');
-    expect(staking.stakes[0].staking.lastClaimed.toNumber()).to.be.equal(start);
+    let staking = await program.account.position.fetch(position);
+    expect(staking.status).to.have.property('staking');
+    expect(staking.status.staking.lastClaimed.toNumber()).to.be.equal(start);
 
     // Claim some amount
     await waitUntilblockTime(provider, start + 2);
@@ -156,12 +165,14 @@ describe("one user staking", () => {
         pool: pool.publicKey,
         authority: user.publicKey,
         to: ata,
-      }).signers([user]).rpc({commitment:'confirmed'});
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [position]))
+      .signers([user]).rpc({commitment:'confirmed'});
 
     const middle = await blockTimeFromTx(provider, tx);
-    staking = await stakingAccount(program, pool.publicKey, user.publicKey);
-    expect(staking.stakes[0]).to.have.property('staking');
-    expect(staking.stakes[0].staking.lastClaimed.toNumber()).to.be.equal(middle);
+    staking = await program.account.position.fetch(position);
+    expect(staking.status).to.have.property('staking');
+    expect(staking.status.staking.lastClaimed.toNumber()).to.be.equal(middle);
 
     const ataMiddle = await tokenBalance(spl_program, ata);
     expect(ataMiddle - ataBegin).to.be.equal(1_000_000 * (middle - start));
@@ -173,24 +184,27 @@ describe("one user staking", () => {
         pool: pool.publicKey,
         authority: user.publicKey,
         to: ata,
-      }).signers([user]).rpc({commitment:'confirmed'});
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [position]))
+      .signers([user]).rpc({commitment:'confirmed'});
 
-    staking = await stakingAccount(program, pool.publicKey, user.publicKey);
-    expect(staking.stakes[0]).to.have.property('ready');
+    staking = await program.account.position.fetch(position);
+    expect(staking.status).to.have.property('ready');
 
     const ataEnd = await tokenBalance(spl_program, ata);
     expect(ataEnd - ataBegin).to.be.equal(5_000_000);
 
     // Unstake
-    await program.methods.unstake({tier500:{}})
+    await program.methods.unstake()
       .accounts({
         pool: pool.publicKey,
         authority: user.publicKey,
+        position,
         to: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
       }).signers([user]).rpc({commitment:'confirmed'});
 
-    staking = await stakingAccount(program, pool.publicKey, user.publicKey);
-    expect(staking.stakes[0]).to.have.property('used');
+    expect(await program.account.position.fetchNullable(position)).to.be.null;
 
     const ataAfter = await tokenBalance(spl_program, ata);
     expect(ataAfter - ataBefore).to.be.equal(5_000_000);
@@ -201,20 +215,28 @@ describe("one user staking", () => {
     const ata = await getATA(user.publicKey, mint.publicKey);
     const ataBefore = await tokenBalance(spl_program, ata);
 
+    const position1 = await positionAddress(program, pool.publicKey, user.publicKey, 1);
+    const position2 = await positionAddress(program, pool.publicKey, user.publicKey, 2);
+    const positions = await positionMetas(program, user.publicKey, [position1, position2]);
+
     // Stake 10 and 15
-    let tx = await program.methods.stake({tier1000:{}})
+    let tx = await program.methods.stake(1, new BN(10_000_000))
       .accounts({
         pool: pool.publicKey,
         authority: user.publicKey,
+        position: position1,
         from: ata,
+        ...await receiptAccounts(program, position1, user.publicKey),
       })
       .postInstructions(
         [
-          await program.methods.stake({tier1500:{}})
+          await program.methods.stake(2, new BN(15_000_000))
             .accounts({
               pool: pool.publicKey,
               authority: user.publicKey,
+              position: position2,
               from: ata,
+              ...await receiptAccounts(program, position2, user.publicKey),
             }).instruction()
         ]
       )
@@ -222,13 +244,14 @@ describe("one user staking", () => {
       .rpc({commitment:'confirmed'});
 
     const start = await blockTimeFromTx(provider, tx);
-    let staking = await stakingAccount(program, pool.publicKey, user.publicKey);
-    expect(staking.stakes[1]).to.have.property('staking');
-    expect(staking.stakes[1].staking.lastClaimed.toNumber()).to.be.equal(start);
-    expect(staking.stakes[1].staking.lockedUntil.toNumber()).to.be.equal(start + 10);
-    expect(staking.stakes[2]).to.have.property('staking');
-    expect(staking.stakes[2].staking.lastClaimed.toNumber()).to.be.equal(start);
-    expect(staking.stakes[2].staking.lockedUntil.toNumber()).to.be.equal(start + 15);
+    let staking1 = await program.account.position.fetch(position1);
+    let staking2 = await program.account.position.fetch(position2);
+    expect(staking1.status).to.have.property('staking');
+    expect(staking1.status.staking.lastClaimed.toNumber()).to.be.equal(start);
+    expect(staking1.status.staking.lockedUntil.toNumber()).to.be.equal(start + 10);
+    expect(staking2.status).to.have.property('staking');
+    expect(staking2.status.staking.lastClaimed.toNumber()).to.be.equal(start);
+    expect(staking2.status.staking.lockedUntil.toNumber()).to.be.equal(start + 15);
 
     const ataStake = await tokenBalance(spl_program, ata);
     expect(ataBefore - ataStake).to.be.equal(25_000_000);
@@ -240,14 +263,17 @@ describe("one user staking", () => {
         pool: pool.publicKey,
         authority: user.publicKey,
         to: ata,
-      }).signers([user]).rpc({commitment:'confirmed'});
+      })
+      .remainingAccounts(positions)
+      .signers([user]).rpc({commitment:'confirmed'});
 
     const middle1 = await blockTimeFromTx(provider, tx);
-    staking = await stakingAccount(program, pool.publicKey, user.publicKey);
-    expect(staking.stakes[1]).to.have.property('staking');
-    expect(staking.stakes[1].staking.lastClaimed.toNumber()).to.be.equal(middle1);
-    expect(staking.stakes[2]).to.have.property('staking');
-    expect(staking.stakes[2].staking.lastClaimed.toNumber()).to.be.equal(middle1);
+    staking1 = await program.account.position.fetch(position1);
+    staking2 = await program.account.position.fetch(position2);
+    expect(staking1.status).to.have.property('staking');
+    expect(staking1.status.staking.lastClaimed.toNumber()).to.be.equal(middle1);
+    expect(staking2.status).to.have.property('staking');
+    expect(staking2.status.staking.lastClaimed.toNumber()).to.be.equal(middle1);
 
     const ataClaim1 = await tokenBalance(spl_program, ata);
     expect(ataClaim1 - ataStake).to.be.equal(2_000_000 * (middle1 - start));
@@ -259,13 +285,16 @@ describe("one user staking", () => {
         pool: pool.publicKey,
         authority: user.publicKey,
         to: ata,
-      }).signers([user]).rpc({commitment:'confirmed'});
+      })
+      .remainingAccounts(positions)
+      .signers([user]).rpc({commitment:'confirmed'});
 
     const middle2 = await blockTimeFromTx(provider, tx);
-    staking = await stakingAccount(program, pool.publicKey, user.publicKey);
-    expect(staking.stakes[1]).to.have.property('ready');
-    expect(staking.stakes[2]).to.have.property('staking');
-    expect(staking.stakes[2].staking.lastClaimed.toNumber()).to.be.equal(middle2);
+    staking1 = await program.account.position.fetch(position1);
+    staking2 = await program.account.position.fetch(position2);
+    expect(staking1.status).to.have.property('ready');
+    expect(staking2.status).to.have.property('staking');
+    expect(staking2.status.staking.lastClaimed.toNumber()).to.be.equal(middle2);
 
     const ataClaim2 = await tokenBalance(spl_program, ata);
     expect(ataClaim2 - ataStake).to.be.equal(10_000_000 + 1_000_000 * (middle2 - start));
@@ -277,27 +306,32 @@ describe("one user staking", () => {
         pool: pool.publicKey,
         authority: user.publicKey,
         to: ata,
-      }).postInstructions(
+      })
+      .remainingAccounts(positions)
+      .postInstructions(
         [
-          await program.methods.unstake({tier1000:{}})
+          await program.methods.unstake()
             .accounts({
               pool: pool.publicKey,
               authority: user.publicKey,
+              position: position1,
               to: ata,
+              ...await receiptAccounts(program, position1, user.publicKey),
             }).instruction(),
-          await program.methods.unstake({tier1500:{}})
+          await program.methods.unstake()
             .accounts({
               pool: pool.publicKey,
               authority: user.publicKey,
+              position: position2,
               to: ata,
+              ...await receiptAccounts(program, position2, user.publicKey),
             }).instruction(),
         ]
       )
       .signers([user]).rpc({commitment:'confirmed'});
 
-    staking = await stakingAccount(program, pool.publicKey, user.publicKey);
-    expect(staking.stakes[1]).to.have.property('used');
-    expect(staking.stakes[2]).to.have.property('used');
+    expect(await program.account.position.fetchNullable(position1)).to.be.null;
+    expect(await program.account.position.fetchNullable(position2)).to.be.null;
 
     const ataEnd = await tokenBalance(spl_program, ata);
     expect(ataEnd - ataBefore).to.be.equal(25_000_000);
@@ -306,11 +340,13 @@ describe("one user staking", () => {
   it("Should NOT stake if no available slots", async () => {
     const ata = await getATA(user2.publicKey, mint.publicKey);
 
-    await program.methods.stake({tier500:{}})
+    await program.methods.stake(0, new BN(5_000_000))
       .accounts({
         pool: pool.publicKey,
         authority: user2.publicKey,
+        position: await positionAddress(program, pool.publicKey, user2.publicKey, 0),
         from: ata,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user2.publicKey, 0), user2.publicKey),
       })
       .preInstructions(
         [
@@ -329,11 +365,13 @@ describe("one user staking", () => {
 
     const ata3 = await getATA(user3.publicKey, mint.publicKey);
     await expect(
-      program.methods.stake({tier500:{}})
+      program.methods.stake(0, new BN(5_000_000))
       .accounts({
         pool: pool.publicKey,
         authority: user3.publicKey,
+        position: await positionAddress(program, pool.publicKey, user3.publicKey, 0),
         from: ata3,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user3.publicKey, 0), user3.publicKey),
       })
       .preInstructions(
         [
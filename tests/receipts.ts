@@ -0,0 +1,286 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import { createAssociatedTokenAccountInstruction } from "@solana/spl-token";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  createToken,
+  mintTo,
+  mintToATA, tokenBalance, positionAddress, receiptAccounts, positionMetas, stakingAccount, waitUntilblockTime, getATA
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("position receipts", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  const user = Keypair.generate();
+  const buyer = Keypair.generate();
+
+  before(async () => {
+    const funderToken = Keypair.generate();
+
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+    await createToken(spl_program, funderToken, mint.publicKey, provider.wallet.publicKey);
+    await mintTo(spl_program, 1_000_000_000, mint.publicKey, funderToken.publicKey, provider.wallet.publicKey);
+
+    const tiers = [
+      {
+        supply: 5,
+        slots: 5,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await spl_program.methods.transfer(new BN(100_000_000))
+      .accounts({
+        source: funderToken.publicKey,
+        destination: rewardVault,
+        authority: provider.wallet.publicKey,
+      })
+      .postInstructions(
+        [
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: user.publicKey
+          }),
+        ]
+      )
+      .rpc();
+
+    await mintToATA(spl_program, user.publicKey, new BN(5_000_000), mint.publicKey, provider.wallet.publicKey);
+    await mintToATA(spl_program, buyer.publicKey, new BN(0), mint.publicKey, provider.wallet.publicKey);
+  });
+
+  async function userAddress() {
+    const [address, _nonce] = await PublicKey.findProgramAddress(
+      [pool.publicKey.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    return address;
+  }
+
+  it("Should mint receipt on stake", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+    const receipt = await receiptAccounts(program, position, user.publicKey);
+
+    await program.methods.stake(0, new BN(5_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: ata,
+        ...receipt,
+      })
+      .preInstructions(
+        [
+          await program.methods.createUser()
+            .accounts({
+              pool: pool.publicKey,
+              authority: user.publicKey,
+            }).instruction()
+        ]
+      )
+      .signers([user])
+      .rpc();
+
+    expect(await tokenBalance(spl_program, receipt.receipt)).to.be.equal(1);
+
+    const receiptMint = await spl_program.account.mint.fetch(receipt.receiptMint);
+    expect(receiptMint.supply.toNumber()).to.be.equal(1);
+    expect(receiptMint.decimals).to.be.equal(0);
+    expect(receiptMint.mintAuthority).to.be.null;
+  });
+
+  it("Should move position rights with receipt", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+    const buyerAta = await getATA(buyer.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+    const receipt = await receiptAccounts(program, position, user.publicKey);
+    const buyerReceipt = await receiptAccounts(program, position, buyer.publicKey);
+
+    await spl_program.methods.transfer(new BN(1))
+      .accounts({
+        source: receipt.receipt,
+        destination: buyerReceipt.receipt,
+        authority: user.publicKey,
+      })
+      .preInstructions([
+        createAssociatedTokenAccountInstruction(
+          provider.wallet.publicKey,
+          buyerReceipt.receipt,
+          buyer.publicKey,
+          receipt.receiptMint)
+      ])
+      .signers([user])
+      .rpc();
+
+    await expect(program.methods.claim()
+      .accounts({
+        pool: pool.publicKey,
+        user: await userAddress(),
+        authority: user.publicKey,
+        to: ata,
+      })
+      .remainingAccounts(await positionMetas(program, buyer.publicKey, [position]))
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/Signer is not the receipt holder or user delegate/);
+
+    const staking = await program.account.position.fetch(position);
+    await waitUntilblockTime(provider, staking.status.staking.lockedUntil.toNumber() + 1);
+
+    await program.methods.claim()
+      .accounts({
+        pool: pool.publicKey,
+        user: await userAddress(),
+        authority: buyer.publicKey,
+        to: buyerAta,
+      })
+      .remainingAccounts(await positionMetas(program, buyer.publicKey, [position]))
+      .signers([buyer])
+      .rpc();
+
+    expect(await tokenBalance(spl_program, buyerAta)).to.be.equal(5_000_000);
+
+    await expect(program.methods.unstake()
+      .accounts({
+        pool: pool.publicKey,
+        user: await userAddress(),
+        authority: user.publicKey,
+        position,
+        to: ata,
+        ...receipt,
+      })
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/Signer doesn't hold the position receipt/);
+  });
+
+  it("Should burn receipt on unstake", async () => {
+    const buyerAta = await getATA(buyer.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+    const buyerReceipt = await receiptAccounts(program, position, buyer.publicKey);
+
+    await program.methods.unstake()
+      .accounts({
+        pool: pool.publicKey,
+        user: await userAddress(),
+        authority: buyer.publicKey,
+        position,
+        to: buyerAta,
+        ...buyerReceipt,
+      })
+      .signers([buyer])
+      .rpc();
+
+    expect(await tokenBalance(spl_program, buyerAta)).to.be.equal(10_000_000);
+    expect(await spl_program.account.token.fetchNullable(buyerReceipt.receipt)).to.be.null;
+
+    const receiptMint = await spl_program.account.mint.fetch(buyerReceipt.receiptMint);
+    expect(receiptMint.supply.toNumber()).to.be.equal(0);
+
+    const staking = await stakingAccount(program, pool.publicKey, user.publicKey);
+    expect(staking.positions[0]).to.be.equal(0);
+  });
+
+  it("Should stake if receipt mint address is funded ahead", async () => {
+    const ata = await mintToATA(spl_program, user.publicKey, new BN(5_000_000), mint.publicKey, provider.wallet.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 1);
+    const receipt = await receiptAccounts(program, position, user.publicKey);
+
+    // Lamports sent to the predictable receipt mint address must not block the stake
+    await provider.sendAndConfirm(new web3.Transaction().add(
+      web3.SystemProgram.transfer({
+        fromPubkey: provider.wallet.publicKey,
+        lamports: 1,
+        toPubkey: receipt.receiptMint
+      })
+    ));
+
+    await program.methods.stake(0, new BN(5_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: ata,
+        ...receipt,
+      })
+      .signers([user])
+      .rpc();
+
+    expect(await tokenBalance(spl_program, receipt.receipt)).to.be.equal(1);
+  });
+
+  it("Should initialize pool if vault addresses are funded ahead", async () => {
+    const fundedPool = Keypair.generate();
+    const transfers = [];
+    for (const seed of ["vault", "reward"]) {
+      const [address, _nonce] = await PublicKey.findProgramAddress(
+        [anchor.utils.bytes.utf8.encode(seed), fundedPool.publicKey.toBuffer()],
+        program.programId
+      );
+      transfers.push(web3.SystemProgram.transfer({
+        fromPubkey: provider.wallet.publicKey,
+        lamports: 1,
+        toPubkey: address
+      }));
+    }
+    await provider.sendAndConfirm(new web3.Transaction().add(...transfers));
+
+    const tiers = [
+      {
+        supply: 1,
+        slots: 1,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
+      },
+    ];
+    await initializeProgram(program, fundedPool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const poolAccount = await program.account.pool.fetch(fundedPool.publicKey);
+    expect(await tokenBalance(spl_program, poolAccount.vault)).to.be.equal(0);
+    expect(await tokenBalance(spl_program, poolAccount.rewardVault)).to.be.equal(0);
+  });
+});
@@ -0,0 +1,262 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  mintToATA, positionAddress, receiptAccounts, positionMetas, tokenBalance, waitUntilblockTime, blockTimeFromTx, getATA
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("separate reward mint", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+  const rewardMint = Keypair.generate();
+
+  const user = Keypair.generate();
+
+  before(async () => {
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+    await creatMintIfRequired(spl_program, rewardMint, provider.wallet.publicKey);
+
+    const tiers = [
+      {
+        supply: 3,
+        slots: 3,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(5),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers, rewardMint.publicKey);
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await spl_program.methods.mintTo(new BN(1_000_000_000))
+      .accounts({
+        mint: rewardMint.publicKey,
+        to: rewardVault,
+        authority: provider.wallet.publicKey,
+      })
+      .postInstructions(
+        [
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: user.publicKey
+          }),
+        ]
+      )
+      .rpc();
+
+    await mintToATA(spl_program, user.publicKey, new BN(5_000_000), mint.publicKey, provider.wallet.publicKey);
+    await mintToATA(spl_program, user.publicKey, new BN(0), rewardMint.publicKey, provider.wallet.publicKey);
+  });
+
+  it("Should store reward mint", async () => {
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.mint).to.be.deep.equal(mint.publicKey);
+    expect(poolAccount.rewardMint).to.be.deep.equal(rewardMint.publicKey);
+  });
+
+  it("Should stake and claim reward in reward mint", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+    const rewardAta = await getATA(user.publicKey, rewardMint.publicKey);
+
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+
+    const tx = await program.methods.stake(0, new BN(5_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .preInstructions(
+        [
+          await program.methods.createUser()
+            .accounts({
+              pool: pool.publicKey,
+              authority: user.publicKey,
+            }).instruction()
+        ]
+      )
+      .signers([user])
+      .rpc({commitment:'confirmed'});
+
+    const start = await blockTimeFromTx(provider, tx);
+    await waitUntilblockTime(provider, start + 6);
+
+    await expect(program.methods.claim()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        to: ata,
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [position]))
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/Invalid mint/);
+
+    await program.methods.claim()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        to: rewardAta,
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [position]))
+      .signers([user])
+      .rpc();
+
+    expect(await tokenBalance(spl_program, rewardAta)).to.be.equal(5_000_000);
+  });
+
+  it("Should NOT unstake to reward mint account", async () => {
+    const rewardAta = await getATA(user.publicKey, rewardMint.publicKey);
+
+    await expect(program.methods.unstake()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position: await positionAddress(program, pool.publicKey, user.publicKey, 0),
+        to: rewardAta,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user.publicKey, 0), user.publicKey),
+      })
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/Invalid mint/);
+  });
+
+  describe("penalties", () => {
+    const penaltyPool = Keypair.generate();
+
+    function tier(penalty: number) {
+      return {
+        supply: 3,
+        slots: 3,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(1000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
+        penalty,
+        forfeitReward: true,
+        maxPerWallet: 0,
+      };
+    }
+
+    it("Should NOT initialize penalty tier without treasury", async () => {
+      await expect(initializeProgram(program, Keypair.generate(), authority.publicKey,
+        provider.wallet.publicKey, mint.publicKey, [tier(10)], rewardMint.publicKey)
+      ).to.be.rejectedWith(/Penalty requires a treasury if reward mint differs from mint/);
+    });
+
+    it("Should NOT set penalty without treasury", async () => {
+      await initializeProgram(program, penaltyPool, authority.publicKey,
+        provider.wallet.publicKey, mint.publicKey, [tier(0)], rewardMint.publicKey);
+
+      const { slots, ...config } = tier(10);
+      await expect(program.methods.updateTier(0, { config })
+        .accounts({
+          pool: penaltyPool.publicKey,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc()).to.be.rejectedWith(/Penalty requires a treasury if reward mint differs from mint/);
+    });
+
+    it("Should early unstake with penalty to treasury", async () => {
+      const treasury = await mintToATA(spl_program, authority.publicKey, new BN(0), mint.publicKey, provider.wallet.publicKey);
+
+      await program.methods.setTreasury()
+        .accounts({
+          pool: penaltyPool.publicKey,
+          authority: authority.publicKey,
+          treasury,
+        })
+        .signers([authority])
+        .rpc();
+
+      const { slots, ...config } = tier(10);
+      await program.methods.updateTier(0, { config })
+        .accounts({
+          pool: penaltyPool.publicKey,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const ata = await mintToATA(spl_program, user.publicKey, new BN(5_000_000), mint.publicKey, provider.wallet.publicKey);
+      const rewardAta = await getATA(user.publicKey, rewardMint.publicKey);
+      const position = await positionAddress(program, penaltyPool.publicKey, user.publicKey, 0);
+
+      await program.methods.stake(0, new BN(5_000_000))
+        .accounts({
+          pool: penaltyPool.publicKey,
+          authority: user.publicKey,
+          position,
+          from: ata,
+          ...await receiptAccounts(program, position, user.publicKey),
+        })
+        .preInstructions(
+          [
+            await program.methods.createUser()
+              .accounts({
+                pool: penaltyPool.publicKey,
+                authority: user.publicKey,
+              }).instruction()
+          ]
+        )
+        .signers([user])
+        .rpc();
+
+      const ataBefore = await tokenBalance(spl_program, ata);
+
+      await program.methods.earlyUnstake()
+        .accounts({
+          pool: penaltyPool.publicKey,
+          authority: user.publicKey,
+          position,
+          to: ata,
+          rewardTo: rewardAta,
+          penaltyTo: treasury,
+          ...await receiptAccounts(program, position, user.publicKey),
+        })
+        .signers([user])
+        .rpc();
+
+      expect(await tokenBalance(spl_program, ata) - ataBefore).to.be.equal(4_500_000);
+      expect(await tokenBalance(spl_program, treasury)).to.be.equal(500_000);
+    });
+  });
+});
@@ -0,0 +1,145 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  mintTo,
+  mintToATA, pause, unpause, close, open, tokenBalance
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("admin roles", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+  const pauser = Keypair.generate();
+  const operator = Keypair.generate();
+  const treasurer = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  before(async () => {
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+
+    const tiers = [
+      {
+        supply: 3,
+        slots: 3,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(600_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+  });
+
+  async function setRole(role, key: PublicKey | null) {
+    return program.methods.setRole(role, key)
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  async function withdrawExtra(signer: Keypair, to: PublicKey) {
+    return program.methods.withdrawExtra()
+      .accounts({
+        pool: pool.publicKey,
+        authority: signer.publicKey,
+        to,
+        rewardTo: to,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  it("Should NOT set role by non authority", async () => {
+    await expect(program.methods.setRole({ pauser: {} }, pauser.publicKey)
+      .accounts({
+        pool: pool.publicKey,
+        authority: pauser.publicKey,
+      })
+      .signers([pauser])
+      .rpc()).to.be.rejectedWith(/A has one constraint was violated/);
+  });
+
+  it("Should pause only by pauser or authority", async () => {
+    await setRole({ pauser: {} }, pauser.publicKey);
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.pauser).to.be.deep.equal(pauser.publicKey);
+    expect(poolAccount.operator).to.be.null;
+
+    await expect(pause(program, pool.publicKey, operator)).to.be.rejectedWith(/Signer doesn't have the role for this action/);
+    await pause(program, pool.publicKey, authority);
+    await unpause(program, pool.publicKey, authority);
+
+    await pause(program, pool.publicKey, pauser);
+
+    // Operator role is not assigned, the authority acts as operator
+    await expect(unpause(program, pool.publicKey, pauser)).to.be.rejectedWith(/Signer doesn't have the role for this action/);
+    await unpause(program, pool.publicKey, authority);
+  });
+
+  it("Should open and close only by operator", async () => {
+    await setRole({ operator: {} }, operator.publicKey);
+
+    await expect(close(program, pool.publicKey, authority)).to.be.rejectedWith(/Signer doesn't have the role for this action/);
+    await close(program, pool.publicKey, operator);
+
+    await expect(open(program, pool.publicKey, pauser)).to.be.rejectedWith(/Signer doesn't have the role for this action/);
+    await open(program, pool.publicKey, operator);
+  });
+
+  it("Should withdraw extra only by treasurer", async () => {
+    await setRole({ treasurer: {} }, treasurer.publicKey);
+    await close(program, pool.publicKey, operator);
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+    await mintTo(spl_program, 1_000_000, mint.publicKey, rewardVault, provider.wallet.publicKey);
+    const ata = await mintToATA(spl_program, treasurer.publicKey, new BN(0), mint.publicKey, provider.wallet.publicKey);
+
+    await expect(withdrawExtra(authority, ata)).to.be.rejectedWith(/Signer doesn't have the role for this action/);
+    await withdrawExtra(treasurer, ata);
+
+    expect(await tokenBalance(spl_program, ata)).to.be.equal(1_000_000);
+  });
+
+  it("Should return role to authority", async () => {
+    await setRole({ pauser: {} }, null);
+
+    await pause(program, pool.publicKey, authority);
+    await unpause(program, pool.publicKey, authority);
+  });
+});
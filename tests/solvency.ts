@@ -0,0 +1,148 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  mintTo,
+  mintToATA, positionAddress, receiptAccounts, stakingAccount, getATA
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("solvency mode", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  const user = Keypair.generate();
+
+  before(async () => {
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+
+    // Reward of each stake is 3_000_000
+    const tiers = [
+      {
+        supply: 5,
+        slots: 5,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(600_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // Enough for one stake only
+    await mintTo(spl_program, 5_000_000, mint.publicKey, rewardVault, provider.wallet.publicKey);
+
+    await program.methods.createUser()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+      })
+      .preInstructions(
+        [
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: user.publicKey
+          }),
+        ]
+      )
+      .signers([user])
+      .rpc();
+
+    await mintToATA(spl_program, user.publicKey, new BN(15_000_000), mint.publicKey, provider.wallet.publicKey);
+  });
+
+  async function stake(index: number) {
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, index);
+
+    return program.methods.stake(0, new BN(5_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: await getATA(user.publicKey, mint.publicKey),
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  async function setSolvencyCheck(enabled: boolean) {
+    return program.methods.setSolvencyCheck(enabled)
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  it("Should NOT enable solvency mode by non authority", async () => {
+    await expect(program.methods.setSolvencyCheck(true)
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+      })
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/A has one constraint was violated/);
+  });
+
+  it("Should stake while reward vault covers the reward", async () => {
+    await setSolvencyCheck(true);
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.solvencyCheck).to.be.true;
+
+    await stake(0);
+  });
+
+  it("Should NOT stake if reward vault can't cover the reward", async () => {
+    await expect(stake(1)).to.be.rejectedWith(/Reward vault can't cover rewards of the new stake/);
+
+    const staking = await stakingAccount(program, pool.publicKey, user.publicKey);
+    expect(staking.positions[0]).to.be.equal(1);
+  });
+
+  it("Should stake without solvency mode", async () => {
+    await setSolvencyCheck(false);
+
+    await stake(1);
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.metrics.rewardRequirements.toNumber()).to.be.equal(6_000_000);
+  });
+});
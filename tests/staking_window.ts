@@ -0,0 +1,183 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  createToken,
+  mintTo,
+  mintToATA, tokenBalance, positionAddress, receiptAccounts, waitUntilblockTime, getATA
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("staking window", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  const user = Keypair.generate();
+  let opensAt: number;
+
+  before(async () => {
+    const funderToken = Keypair.generate();
+
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+    await createToken(spl_program, funderToken, mint.publicKey, provider.wallet.publicKey);
+    await mintTo(spl_program, 1_000_000_000, mint.publicKey, funderToken.publicKey, provider.wallet.publicKey);
+
+    const tiers = [
+      {
+        supply: 1,
+        slots: 1,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(600_000_000),
+        curve: { linear: {} },
+      },
+      {
+        supply: 1,
+        slots: 1,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(600_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    const slot = await provider.connection.getSlot();
+    opensAt = await provider.connection.getBlockTime(slot) + 5;
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers, mint.publicKey, new BN(opensAt), new BN(opensAt + 1000));
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await spl_program.methods.transfer(new BN(100_000_000))
+      .accounts({
+        source: funderToken.publicKey,
+        destination: rewardVault,
+        authority: provider.wallet.publicKey,
+      })
+      .postInstructions(
+        [
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: user.publicKey
+          }),
+        ]
+      )
+      .rpc();
+
+    await mintToATA(spl_program, user.publicKey, new BN(10_000_000), mint.publicKey, provider.wallet.publicKey);
+  });
+
+  async function stake(tier: number, index: number) {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, index);
+
+    return program.methods.stake(tier, new BN(5_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  async function setStakingWindow(opensAt: BN | null, closesAt: BN | null) {
+    return program.methods.setStakingWindow(opensAt, closesAt)
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  it("Should store staking window", async () => {
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.opensAt.toNumber()).to.be.equal(opensAt);
+    expect(poolAccount.closesAt.toNumber()).to.be.equal(opensAt + 1000);
+  });
+
+  it("Should NOT create user before staking window", async () => {
+    await expect(program.methods.createUser()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+      })
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/Pool is not open for staking yet/);
+  });
+
+  it("Should stake in staking window", async () => {
+    await waitUntilblockTime(provider, opensAt + 1);
+
+    await program.methods.createUser()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+      })
+      .signers([user])
+      .rpc();
+
+    await stake(0, 0);
+
+    expect(await tokenBalance(spl_program, await getATA(user.publicKey, mint.publicKey))).to.be.equal(5_000_000);
+  });
+
+  it("Should NOT set window closing before opening", async () => {
+    await expect(setStakingWindow(new BN(opensAt), new BN(opensAt - 1)))
+      .to.be.rejectedWith(/Staking window must open before it closes/);
+  });
+
+  it("Should NOT stake after staking window", async () => {
+    await setStakingWindow(null, new BN(opensAt));
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.opensAt).to.be.null;
+    expect(poolAccount.closed).to.be.false;
+
+    await expect(stake(1, 1)).to.be.rejectedWith(/Pool is closed for new staking/);
+  });
+
+  it("Should close pool when all slots are used", async () => {
+    await setStakingWindow(null, null);
+
+    await stake(1, 1);
+
+    await expect(stake(0, 2)).to.be.rejectedWith(/Pool is closed for new staking/);
+  });
+});
@@ -0,0 +1,248 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  ExtensionType,
+  getMintLen,
+  getMint,
+  getAccount,
+  getTransferFeeConfig,
+  getAssociatedTokenAddress,
+  createInitializeTransferFeeConfigInstruction,
+  createInitializeMintInstruction,
+  createAssociatedTokenAccountInstruction,
+  createMintToInstruction,
+} from "@solana/spl-token";
+import {
+  close, positionAddress, receiptAccounts, positionMetas, waitUntilblockTime, blockTimeFromTx
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+chai.use(chaiAsPromised);
+
+describe("token-2022 transfer fee", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+  const mint = Keypair.generate();
+
+  const user = Keypair.generate();
+  let ata: PublicKey;
+  let vault: PublicKey;
+  let rewardVault: PublicKey;
+  let position: PublicKey;
+
+  // 1% fee of every transfer is withheld in the destination account
+  const feeBasisPoints = 100;
+
+  async function events(tx: string) {
+    const transaction = await provider.connection.getTransaction(tx, {commitment: "confirmed"});
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    const parsed = [];
+    parser.parseLogs(transaction.meta.logMessages, event => parsed.push(event));
+    return parsed;
+  }
+
+  async function mintTo(to: PublicKey, amount: number) {
+    await provider.sendAndConfirm(new web3.Transaction().add(
+      createMintToInstruction(mint.publicKey, to, provider.wallet.publicKey, amount, [], TOKEN_2022_PROGRAM_ID)
+    ));
+  }
+
+  before(async function () {
+    if (await provider.connection.getAccountInfo(TOKEN_2022_PROGRAM_ID) == null) {
+      // Token-2022 program is not deployed on this validator
+      this.skip();
+    }
+
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    await provider.sendAndConfirm(new web3.Transaction().add(
+      web3.SystemProgram.createAccount({
+        fromPubkey: provider.wallet.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space: mintLen,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(mintLen),
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferFeeConfigInstruction(
+        mint.publicKey,
+        provider.wallet.publicKey,
+        provider.wallet.publicKey,
+        feeBasisPoints,
+        BigInt(1_000_000_000),
+        TOKEN_2022_PROGRAM_ID,
+      ),
+      createInitializeMintInstruction(mint.publicKey, 6, provider.wallet.publicKey, null, TOKEN_2022_PROGRAM_ID),
+    ), [mint]);
+
+    // Reward is equal to the staked amount
+    const tiers = [
+      {
+        supply: 1,
+        slots: 1,
+        minStake: new BN(1_000_000),
+        maxStake: new BN(10_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    await program.methods.initialize(tiers, null, null)
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+        payer: provider.wallet.publicKey,
+        mint: mint.publicKey,
+        rewardMint: mint.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        rewardTokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([pool])
+      .rpc();
+
+    [vault] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("vault"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+    [rewardVault] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+
+    ata = await getAssociatedTokenAddress(mint.publicKey, user.publicKey, false, TOKEN_2022_PROGRAM_ID);
+    await provider.sendAndConfirm(new web3.Transaction().add(
+      createAssociatedTokenAccountInstruction(
+        provider.wallet.publicKey, ata, user.publicKey, mint.publicKey, TOKEN_2022_PROGRAM_ID),
+      web3.SystemProgram.transfer({
+        fromPubkey: provider.wallet.publicKey,
+        lamports: web3.LAMPORTS_PER_SOL,
+        toPubkey: user.publicKey
+      }),
+    ));
+    await mintTo(ata, 10_000_000);
+    // Minting is not charged with fee, the reward vault gets reward of the stake exactly
+    await mintTo(rewardVault, 9_900_000);
+
+    position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+  });
+
+  it("Should stake the amount received by vault", async () => {
+    const tx = await program.methods.stake(0, new BN(10_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: ata,
+        mint: mint.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .preInstructions(
+        [
+          await program.methods.createUser()
+            .accounts({
+              pool: pool.publicKey,
+              authority: user.publicKey,
+            }).instruction()
+        ]
+      )
+      .signers([user])
+      .rpc({commitment:'confirmed'});
+
+    const [event] = (await events(tx)).filter(e => e.name == "StakeEvent");
+    expect(event.data.amount.toNumber()).to.be.equal(9_900_000);
+    expect(event.data.fee.toNumber()).to.be.equal(100_000);
+    expect(event.data.reward.toNumber()).to.be.equal(9_900_000);
+
+    const positionAccount = await program.account.position.fetch(position);
+    expect(positionAccount.status.staking.amount.toNumber()).to.be.equal(9_900_000);
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.metrics.staked.toNumber()).to.be.equal(9_900_000);
+
+    const vaultAccount = await getAccount(provider.connection, vault, "confirmed", TOKEN_2022_PROGRAM_ID);
+    expect(Number(vaultAccount.amount)).to.be.equal(9_900_000);
+
+    await waitUntilblockTime(provider, await blockTimeFromTx(provider, tx) + 4);
+  });
+
+  it("Should report fee of claim and unstake", async () => {
+    const claimTx = await program.methods.claim()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        to: ata,
+        rewardMint: mint.publicKey,
+        rewardTokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [position]))
+      .signers([user])
+      .rpc({commitment:'confirmed'});
+
+    const [claimEvent] = (await events(claimTx)).filter(e => e.name == "ClaimEvent");
+    expect(claimEvent.data.amount.toNumber()).to.be.equal(9_900_000);
+    expect(claimEvent.data.fee.toNumber()).to.be.equal(99_000);
+
+    const unstakeTx = await program.methods.unstake()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        to: ata,
+        mint: mint.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .signers([user])
+      .rpc({commitment:'confirmed'});
+
+    const [unstakeEvent] = (await events(unstakeTx)).filter(e => e.name == "UnstakeEvent");
+    expect(unstakeEvent.data.amount.toNumber()).to.be.equal(9_900_000);
+    expect(unstakeEvent.data.fee.toNumber()).to.be.equal(99_000);
+
+    const ataAccount = await getAccount(provider.connection, ata, "confirmed", TOKEN_2022_PROGRAM_ID);
+    expect(Number(ataAccount.amount)).to.be.equal(2 * (9_900_000 - 99_000));
+  });
+
+  it("Should harvest withheld fees when freeing pool", async () => {
+    await close(program, pool.publicKey, authority);
+
+    await program.methods.freePool()
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+        vault,
+        rewardVault,
+        mint: mint.publicKey,
+        rewardMint: mint.publicKey,
+        receiver: provider.wallet.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        rewardTokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(vault)).to.be.null;
+    expect(await provider.connection.getAccountInfo(rewardVault)).to.be.null;
+
+    // Fee withheld in vault on stake is moved to mint
+    const mintAccount = await getMint(provider.connection, mint.publicKey, "confirmed", TOKEN_2022_PROGRAM_ID);
+    expect(Number(getTransferFeeConfig(mintAccount).withheldAmount)).to.be.equal(100_000);
+  });
+});
@@ -0,0 +1,229 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  createToken,
+  mintTo,
+  mintToATA, tokenBalance, stakingAccount, positionAddress, receiptAddress, receiptAccounts, positionAccount, waitUntilblockTime, getATA
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("transfer position", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  const user = Keypair.generate();
+  const recipient = Keypair.generate();
+
+  before(async () => {
+    const funderToken = Keypair.generate();
+
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+    await createToken(spl_program, funderToken, mint.publicKey, provider.wallet.publicKey);
+    await mintTo(spl_program, 1_000_000_000, mint.publicKey, funderToken.publicKey, provider.wallet.publicKey);
+
+    const tiers = [
+      {
+        supply: 5,
+        slots: 5,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(600_000_000),
+        curve: { linear: {} },
+        maxPerWallet: 1,
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await spl_program.methods.transfer(new BN(100_000_000))
+      .accounts({
+        source: funderToken.publicKey,
+        destination: rewardVault,
+        authority: provider.wallet.publicKey,
+      })
+      .postInstructions(
+        [
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: user.publicKey
+          }),
+        ]
+      )
+      .rpc();
+
+    await mintToATA(spl_program, user.publicKey, new BN(10_000_000), mint.publicKey, provider.wallet.publicKey);
+    await mintToATA(spl_program, recipient.publicKey, new BN(0), mint.publicKey, provider.wallet.publicKey);
+  });
+
+  it("Should transfer position to new wallet", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+    const recipientPosition = await positionAddress(program, pool.publicKey, recipient.publicKey, 0);
+
+    await program.methods.stake(0, new BN(5_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .preInstructions(
+        [
+          await program.methods.createUser()
+            .accounts({
+              pool: pool.publicKey,
+              authority: user.publicKey,
+            }).instruction()
+        ]
+      )
+      .signers([user])
+      .rpc();
+
+    const staked = await program.account.position.fetch(position);
+
+    await program.methods.transferPosition()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        recipient: recipient.publicKey,
+        recipientPosition,
+        recipientReceipt: await getATA(recipient.publicKey, await receiptAddress(program, recipientPosition)),
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .preInstructions(
+        [
+          // Funded receipt mint address of the recipient position must not block the transfer
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: 1,
+            toPubkey: await receiptAddress(program, recipientPosition)
+          }),
+        ]
+      )
+      .signers([user])
+      .rpc();
+
+    expect(await positionAccount(program, pool.publicKey, user.publicKey, 0)).to.be.null;
+    const staking = await stakingAccount(program, pool.publicKey, user.publicKey);
+    expect(staking.positions[0]).to.be.equal(0);
+
+    const recipientStaking = await stakingAccount(program, pool.publicKey, recipient.publicKey);
+    expect(recipientStaking.authority).to.be.deep.equal(recipient.publicKey);
+    expect(recipientStaking.positions[0]).to.be.equal(1);
+    expect(recipientStaking.nextPosition.toNumber()).to.be.equal(1);
+
+    const transferred = await program.account.position.fetch(recipientPosition);
+    expect(transferred.tier).to.be.equal(0);
+    expect(transferred.status).to.be.deep.equal(staked.status);
+
+    const receipt = await receiptAccounts(program, position, user.publicKey);
+    expect(await spl_program.account.token.fetchNullable(receipt.receipt)).to.be.null;
+    const recipientReceipt = await receiptAccounts(program, recipientPosition, recipient.publicKey);
+    expect(await tokenBalance(spl_program, recipientReceipt.receipt)).to.be.equal(1);
+  });
+
+  it("Should NOT transfer position to the same wallet", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 1);
+
+    await program.methods.stake(0, new BN(5_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .signers([user])
+      .rpc();
+
+    await expect(program.methods.transferPosition()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        recipient: user.publicKey,
+        recipientPosition: await positionAddress(program, pool.publicKey, user.publicKey, 2),
+        recipientReceipt: await getATA(user.publicKey,
+          await receiptAddress(program, await positionAddress(program, pool.publicKey, user.publicKey, 2))),
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/Position can't be transferred to the same wallet/);
+  });
+
+  it("Should NOT transfer position if recipient tier is full", async () => {
+    await expect(program.methods.transferPosition()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position: await positionAddress(program, pool.publicKey, user.publicKey, 1),
+        recipient: recipient.publicKey,
+        recipientPosition: await positionAddress(program, pool.publicKey, recipient.publicKey, 1),
+        recipientReceipt: await getATA(recipient.publicKey,
+          await receiptAddress(program, await positionAddress(program, pool.publicKey, recipient.publicKey, 1))),
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user.publicKey, 1), user.publicKey),
+      })
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/Max positions per wallet reached for this tier/);
+  });
+
+  it("Should unstake transferred position by recipient", async () => {
+    const recipientAta = await getATA(recipient.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, recipient.publicKey, 0);
+
+    const staking = await program.account.position.fetch(position);
+    await waitUntilblockTime(provider, staking.status.staking.lockedUntil.toNumber() + 1);
+
+    await program.methods.exit()
+      .accounts({
+        pool: pool.publicKey,
+        authority: recipient.publicKey,
+        position,
+        to: recipientAta,
+        rewardTo: recipientAta,
+        ...await receiptAccounts(program, position, recipient.publicKey),
+      })
+      .signers([recipient])
+      .rpc();
+
+    expect(await tokenBalance(spl_program, recipientAta)).to.be.equal(8_000_000);
+  });
+});
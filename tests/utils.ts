@@ -11,15 +11,19 @@ export async function initializeProgram(
   authority: PublicKey,
   funder: PublicKey,
   mint: PublicKey,
-  tiers)
+  tiers,
+  rewardMint: PublicKey = mint,
+  opensAt: BN | null = null,
+  closesAt: BN | null = null)
 {
-  await program.methods.initialize(tiers)
+  return await program.methods.initialize(tiers, opensAt, closesAt)
     .accounts(
       {
         pool: pool.publicKey,
         authority,
         funder,
         mint,
+        rewardMint,
       }
     )
     .signers([pool])
@@ -31,7 +35,7 @@ export async function pause(
   pool: PublicKey,
   authority: Keypair,
 ) {
-  await program.methods.pause()
+  return await program.methods.pause()
     .accounts({
       pool,
       authority: authority.publicKey,
@@ -45,7 +49,7 @@ export async function unpause(
   pool: PublicKey,
   authority: Keypair,
 ) {
-  await program.methods.unpause()
+  return await program.methods.unpause()
     .accounts({
       pool,
       authority: authority.publicKey,
@@ -59,7 +63,7 @@ export async function close(
   pool: PublicKey,
   authority: Keypair,
 ) {
-  await program.methods.close()
+  return await program.methods.close()
     .accounts({
       pool,
       authority: authority.publicKey,
@@ -73,7 +77,7 @@ export async function open(
   pool: PublicKey,
   authority: Keypair,
 ) {
-  await program.methods.open()
+  return await program.methods.open()
     .accounts({
       pool,
       authority: authority.publicKey,
@@ -210,6 +214,60 @@ export async function stakingAccount(program: Program<Staking>,
   return await program.account.user.fetch(user1staking);
 }
 
+export async function positionAddress(program: Program<Staking>,
+                                      pool: PublicKey,
+                                      user: PublicKey,
+                                      index: number,
+) {
+  const [userStaking, _nonce1] = await PublicKey.findProgramAddress(
+    [pool.toBuffer(), user.toBuffer()],
+    program.programId
+  );
+  const [position, _nonce2] = await PublicKey.findProgramAddress(
+    [userStaking.toBuffer(), new BN(index).toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+  return position;
+}
+
+export async function positionAccount(program: Program<Staking>,
+                                      pool: PublicKey,
+                                      user: PublicKey,
+                                      index: number,
+) {
+  return await program.account.position.fetchNullable(
+    await positionAddress(program, pool, user, index)
+  );
+}
+
+export async function receiptAddress(program: Program<Staking>, position: PublicKey) {
+  const [receiptMint, _nonce] = await PublicKey.findProgramAddress(
+    [anchor.utils.bytes.utf8.encode("receipt"), position.toBuffer()],
+    program.programId
+  );
+  return receiptMint;
+}
+
+export async function receiptAccounts(program: Program<Staking>, position: PublicKey, holder: PublicKey) {
+  const receiptMint = await receiptAddress(program, position);
+  return {
+    receiptMint,
+    receipt: await getATA(holder, receiptMint),
+    receiptTokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+    associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+    rent: web3.SYSVAR_RENT_PUBKEY,
+  };
+}
+
+export async function positionMetas(program: Program<Staking>, holder: PublicKey, positions: PublicKey[]) {
+  const metas = [];
+  for (const pubkey of positions) {
+    metas.push({pubkey, isWritable: true, isSigner: false});
+    metas.push({pubkey: await getATA(holder, await receiptAddress(program, pubkey)), isWritable: false, isSigner: false});
+  }
+  return metas;
+}
+
 export async function waitUntilblockTime(provider: AnchorProvider, until: number) {
   const slot = await provider.connection.getSlot();
   const blockTime = await provider.connection.getBlockTime(slot);
@@ -0,0 +1,250 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  createToken,
+  mintTo,
+  mintToATA, tokenBalance, positionAddress, receiptAccounts, positionMetas, waitUntilblockTime, blockTimeFromTx, getATA
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("reward vesting curves", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  const user = Keypair.generate();
+
+  before(async () => {
+    const funderToken = Keypair.generate();
+
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+    await createToken(spl_program, funderToken, mint.publicKey, provider.wallet.publicKey);
+    await mintTo(spl_program, 1_000_000_000, mint.publicKey, funderToken.publicKey, provider.wallet.publicKey);
+
+    const tiers = [
+      {
+        supply: 5,
+        slots: 5,
+        minStake: new BN(6_000_000),
+        maxStake: new BN(6_000_000),
+        duration: new BN(6),
+        rewardRate: new BN(1_000_000_000),
+        curve: { cliff: { cliff: new BN(3) } },
+      },
+      {
+        supply: 5,
+        slots: 5,
+        minStake: new BN(6_000_000),
+        maxStake: new BN(6_000_000),
+        duration: new BN(6),
+        rewardRate: new BN(1_000_000_000),
+        curve: { stepped: { step: new BN(3) } },
+      },
+      {
+        supply: 5,
+        slots: 5,
+        minStake: new BN(6_000_000),
+        maxStake: new BN(6_000_000),
+        duration: new BN(6),
+        rewardRate: new BN(1_000_000_000),
+        curve: { backLoaded: {} },
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await spl_program.methods.transfer(new BN(100_000_000))
+      .accounts({
+        source: funderToken.publicKey,
+        destination: rewardVault,
+        authority: provider.wallet.publicKey,
+      })
+      .postInstructions(
+        [
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: user.publicKey
+          }),
+        ]
+      )
+      .rpc();
+
+    await mintToATA(spl_program, user.publicKey, new BN(18_000_000), mint.publicKey, provider.wallet.publicKey);
+  });
+
+  it("Should NOT vest reward before cliff", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+
+    const tx = await program.methods.stake(0, new BN(6_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .preInstructions(
+        [
+          await program.methods.createUser()
+            .accounts({
+              pool: pool.publicKey,
+              authority: user.publicKey,
+            }).instruction()
+        ]
+      )
+      .signers([user])
+      .rpc({commitment:'confirmed'});
+
+    const start = await blockTimeFromTx(provider, tx);
+    await waitUntilblockTime(provider, start + 1);
+
+    await expect(program.methods.claim()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        to: ata,
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [position]))
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/Amount must be greater than zero/);
+
+    await waitUntilblockTime(provider, start + 3);
+
+    const ataBefore = await tokenBalance(spl_program, ata);
+    const claimTx = await program.methods.claim()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        to: ata,
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [position]))
+      .signers([user])
+      .rpc({commitment:'confirmed'});
+
+    const elapsed = Math.min(await blockTimeFromTx(provider, claimTx) - start, 6);
+    expect(await tokenBalance(spl_program, ata) - ataBefore).to.be.equal(1_000_000 * elapsed);
+  });
+
+  it("Should vest reward in steps", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 1);
+
+    const tx = await program.methods.stake(1, new BN(6_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .signers([user])
+      .rpc({commitment:'confirmed'});
+
+    const start = await blockTimeFromTx(provider, tx);
+    await waitUntilblockTime(provider, start + 3);
+
+    const ataBefore = await tokenBalance(spl_program, ata);
+    const claimTx = await program.methods.claim()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        to: ata,
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [position]))
+      .signers([user])
+      .rpc({commitment:'confirmed'});
+
+    const elapsed = Math.min(await blockTimeFromTx(provider, claimTx) - start, 6);
+    const vested = 6_000_000 * (Math.floor(elapsed / 3) * 3) / 6;
+    expect(await tokenBalance(spl_program, ata) - ataBefore).to.be.equal(vested);
+  });
+
+  it("Should vest back loaded reward quadratically", async () => {
+    const ata = await getATA(user.publicKey, mint.publicKey);
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 2);
+
+    const claim = async () => program.methods.claim()
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        to: ata,
+      })
+      .remainingAccounts(await positionMetas(program, user.publicKey, [position]))
+      .signers([user]);
+
+    // Nothing is vested at the start of the lock
+    await expect(program.methods.stake(2, new BN(6_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .postInstructions([await (await claim()).instruction()])
+      .signers([user])
+      .rpc()).to.be.rejectedWith(/Amount must be greater than zero/);
+
+    const tx = await program.methods.stake(2, new BN(6_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: ata,
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .signers([user])
+      .rpc({commitment:'confirmed'});
+
+    const start = await blockTimeFromTx(provider, tx);
+    await waitUntilblockTime(provider, start + 3);
+
+    const ataBefore = await tokenBalance(spl_program, ata);
+    const claimTx = await (await claim()).rpc({commitment:'confirmed'});
+
+    // A quarter of reward is vested at half of the lock
+    const elapsed = Math.min(await blockTimeFromTx(provider, claimTx) - start, 6);
+    const vested = Math.floor(Math.floor(6_000_000 * elapsed / 6) * elapsed / 6);
+    expect(await tokenBalance(spl_program, ata) - ataBefore).to.be.equal(vested);
+
+    await waitUntilblockTime(provider, start + 7);
+
+    await (await claim()).rpc({commitment:'confirmed'});
+
+    expect(await tokenBalance(spl_program, ata) - ataBefore).to.be.equal(6_000_000);
+  });
+});
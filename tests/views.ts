@@ -0,0 +1,163 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  mintTo,
+  mintToATA, positionAddress, receiptAccounts, waitUntilblockTime, blockTimeFromTx, getATA
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("views", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  const user = Keypair.generate();
+  let userAccount: PublicKey;
+  let position: PublicKey;
+  let stakedAt: number;
+
+  before(async () => {
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+
+    // Reward of the stake is 3_000_000
+    const tiers = [
+      {
+        supply: 3,
+        slots: 3,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(600_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+    await mintTo(spl_program, 10_000_000, mint.publicKey, rewardVault, provider.wallet.publicKey);
+
+    await mintToATA(spl_program, user.publicKey, new BN(5_000_000), mint.publicKey, provider.wallet.publicKey);
+
+    [userAccount] = await PublicKey.findProgramAddress(
+      [pool.publicKey.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+
+    const tx = await program.methods.stake(0, new BN(5_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: await getATA(user.publicKey, mint.publicKey),
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .preInstructions(
+        [
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: user.publicKey
+          }),
+          await program.methods.createUser()
+            .accounts({
+              pool: pool.publicKey,
+              authority: user.publicKey,
+            }).instruction()
+        ]
+      )
+      .signers([user])
+      .rpc({commitment:'confirmed'});
+    stakedAt = await blockTimeFromTx(provider, tx);
+  });
+
+  // Return data is logged by runtime as "Program return: <program id> <base64 data>"
+  function returnData(logs: string[], typeName: string) {
+    const prefix = `Program return: ${program.programId.toBase58()} `;
+    const log = logs.find(log => log.startsWith(prefix));
+    expect(log).to.not.be.undefined;
+    return program.coder.types.decode(typeName, Buffer.from(log.slice(prefix.length), "base64"));
+  }
+
+  async function pendingRewards(positions: PublicKey[]) {
+    const result = await program.methods.pendingRewards()
+      .accounts({
+        pool: pool.publicKey,
+        user: userAccount,
+      })
+      .remainingAccounts(positions.map(pubkey => ({pubkey, isWritable: false, isSigner: false})))
+      .simulate();
+    return returnData(result.raw as string[], "UserRewards");
+  }
+
+  it("Should return pool summary", async () => {
+    const result = await program.methods.poolSummary()
+      .accounts({
+        pool: pool.publicKey,
+      })
+      .simulate();
+    const stats = returnData(result.raw as string[], "PoolStats");
+
+    expect(stats.open).to.be.true;
+    expect(stats.paused).to.be.false;
+    expect(stats.metrics.staked.toNumber()).to.be.equal(5_000_000);
+    expect(stats.metrics.rewardRequirements.toNumber()).to.be.equal(3_000_000);
+    expect(stats.vaultAmount.toNumber()).to.be.equal(5_000_000);
+    expect(stats.rewardVaultAmount.toNumber()).to.be.equal(10_000_000);
+    expect(stats.extraReward.toNumber()).to.be.equal(7_000_000);
+    expect(stats.maxPayable.toNumber()).to.be.equal(9_000_000);
+    expect(stats.slots).to.be.deep.equal([2]);
+  });
+
+  it("Should return pending rewards of user positions", async () => {
+    await waitUntilblockTime(provider, stakedAt + 4);
+
+    const rewards = await pendingRewards([position]);
+
+    expect(rewards.user).to.be.deep.equal(userAccount);
+    expect(rewards.total.toNumber()).to.be.equal(3_000_000);
+    expect(rewards.positions.length).to.be.equal(1);
+    expect(rewards.positions[0].position).to.be.deep.equal(position);
+    expect(rewards.positions[0].amount.toNumber()).to.be.equal(3_000_000);
+    expect(rewards.positions[0].unlocked).to.be.true;
+
+    // Nothing is claimed by the view
+    const positionAccount = await program.account.position.fetch(position);
+    expect(positionAccount.status.staking.rewardPaid.toNumber()).to.be.equal(0);
+  });
+
+  it("Should NOT return pending rewards of the same position twice", async () => {
+    await expect(pendingRewards([position, position])).to.be.rejectedWith(/Invalid position/);
+  });
+});
@@ -11,7 +11,7 @@ import {
   creatMintIfRequired,
   createToken,
   mintTo,
-  mintToATA, tokenBalance, stakingAccount, waitUntilblockTime, blockTimeFromTx, vaultBalance, rewardBalance, getATA
+  mintToATA, positionAddress, receiptAccounts, tokenBalance, stakingAccount, waitUntilblockTime, blockTimeFromTx, vaultBalance, rewardBalance, getATA
 } from './utils';
 
 import { expect } from 'chai';
@@ -57,23 +57,29 @@ describe("withdraw", () => {
       {
         supply: 3,
         slots: 3,
-        stake: new BN(5_000_000),
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
         duration: new BN(5),
-        reward: new BN(5_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       },
       {
         supply: 3,
         slots: 3,
-        stake: new BN(10_000_000),
+        minStake: new BN(10_000_000),
+        maxStake: new BN(10_000_000),
         duration: new BN(10),
-        reward: new BN(10_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       },
       {
         supply: 3,
         slots: 3,
-        stake: new BN(15_000_000),
+        minStake: new BN(15_000_000),
+        maxStake: new BN(15_000_000),
         duration: new BN(15),
-        reward: new BN(15_000_000),
+        rewardRate: new BN(1_000_000_000),
+        curve: { linear: {} },
       },
     ];
 
@@ -126,6 +132,7 @@ describe("withdraw", () => {
         pool: pool.publicKey,
         authority: authority.publicKey,
         to: ata,
+        rewardTo: ata,
       }).signers([authority])
       .rpc()
     ).to.be.rejected;
@@ -141,6 +148,7 @@ describe("withdraw", () => {
         pool: pool.publicKey,
         authority: authority.publicKey,
         to: ata,
+        rewardTo: ata,
       }).signers([authority])
       .rpc();
 
@@ -165,11 +173,13 @@ describe("withdraw", () => {
       }).signers([authority]).rpc();
 
     const userAta = await getATA(user.publicKey, mint.publicKey);
-    await program.methods.stake({tier1000:{}})
+    await program.methods.stake(1, new BN(10_000_000))
       .accounts({
         pool: pool.publicKey,
         authority: user.publicKey,
+        position: await positionAddress(program, pool.publicKey, user.publicKey, 0),
         from: userAta,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user.publicKey, 0), user.publicKey),
       }).signers([user])
       .preInstructions([
         await program.methods.createUser()
@@ -187,6 +197,7 @@ describe("withdraw", () => {
         pool: pool.publicKey,
         authority: authority.publicKey,
         to: funderAta,
+        rewardTo: funderAta,
       }).signers([authority])
       .rpc();
 
@@ -206,6 +217,7 @@ describe("withdraw", () => {
         pool: pool.publicKey,
         authority: authority.publicKey,
         to: funderAta,
+        rewardTo: funderAta,
       }).signers([authority])
       .rpc()).to.be.rejectedWith(/Pool is paused/);
 
@@ -229,11 +241,13 @@ describe("withdraw", () => {
       }).signers([authority]).rpc();
 
     const userAta = await getATA(user.publicKey, mint.publicKey);
-    await program.methods.stake({tier1500:{}})
+    await program.methods.stake(2, new BN(15_000_000))
       .accounts({
         pool: pool.publicKey,
         authority: user.publicKey,
+        position: await positionAddress(program, pool.publicKey, user.publicKey, 1),
         from: userAta,
+        ...await receiptAccounts(program, await positionAddress(program, pool.publicKey, user.publicKey, 1), user.publicKey),
       }).signers([user])
       .rpc();
 
@@ -244,6 +258,7 @@ describe("withdraw", () => {
         pool: pool.publicKey,
         authority: authority.publicKey,
         to: funderAta,
+        rewardTo: funderAta,
       }).signers([authority])
       .rpc()).to.be.rejectedWith(/Only extra \(total - required\) withdraw allowed/)
 
@@ -0,0 +1,132 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  mintTo,
+  mintToATA, positionAddress, receiptAccounts, rewardBalance, tokenBalance, getATA
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("withdraw amount", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  const user = Keypair.generate();
+  let ata: PublicKey;
+
+  before(async () => {
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+
+    // Reward of the stake is 3_000_000
+    const tiers = [
+      {
+        supply: 3,
+        slots: 3,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(100),
+        rewardRate: new BN(600_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+    await mintTo(spl_program, 5_000_000, mint.publicKey, rewardVault, provider.wallet.publicKey);
+
+    await mintToATA(spl_program, user.publicKey, new BN(5_000_000), mint.publicKey, provider.wallet.publicKey);
+    ata = await mintToATA(spl_program, authority.publicKey, new BN(0), mint.publicKey, provider.wallet.publicKey);
+
+    const position = await positionAddress(program, pool.publicKey, user.publicKey, 0);
+    await program.methods.stake(0, new BN(5_000_000))
+      .accounts({
+        pool: pool.publicKey,
+        authority: user.publicKey,
+        position,
+        from: await getATA(user.publicKey, mint.publicKey),
+        ...await receiptAccounts(program, position, user.publicKey),
+      })
+      .preInstructions(
+        [
+          web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            lamports: web3.LAMPORTS_PER_SOL,
+            toPubkey: user.publicKey
+          }),
+          await program.methods.createUser()
+            .accounts({
+              pool: pool.publicKey,
+              authority: user.publicKey,
+            }).instruction()
+        ]
+      )
+      .signers([user])
+      .rpc();
+  });
+
+  async function withdrawAmount(vault, amount: number) {
+    return program.methods.withdrawAmount(vault, new BN(amount))
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+        to: ata,
+        rewardTo: ata,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  it("Should withdraw part of extra reward while pool is open", async () => {
+    await withdrawAmount({ reward: {} }, 1_500_000);
+
+    expect(await tokenBalance(spl_program, ata)).to.be.equal(1_500_000);
+    expect(await rewardBalance(spl_program, pool.publicKey, program.programId)).to.be.equal(3_500_000);
+  });
+
+  it("Should NOT withdraw reward required for stakers", async () => {
+    await expect(withdrawAmount({ reward: {} }, 500_001))
+      .to.be.rejectedWith(/Only extra \(total - required\) withdraw allowed/);
+
+    await withdrawAmount({ reward: {} }, 500_000);
+    expect(await rewardBalance(spl_program, pool.publicKey, program.programId)).to.be.equal(3_000_000);
+  });
+
+  it("Should NOT withdraw zero amount", async () => {
+    await expect(withdrawAmount({ reward: {} }, 0)).to.be.rejectedWith(/Amount must be greater than zero/);
+  });
+
+  it("Should NOT withdraw from stake vault while pool is open", async () => {
+    await expect(withdrawAmount({ stake: {} }, 1)).to.be.rejectedWith(/PoolHasToBeClosed/);
+  });
+});
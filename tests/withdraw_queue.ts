@@ -0,0 +1,198 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { Program, web3, BN } from "@project-serum/anchor";
+import { Staking } from "../target/types/staking";
+import {
+  initializeProgram,
+  creatMintIfRequired,
+  mintTo,
+  mintToATA, close, tokenBalance, waitUntilblockTime
+} from './utils';
+
+import { expect } from 'chai';
+import * as chai from 'chai';
+import chaiAsPromised from 'chai-as-promised';
+
+import * as fs from "fs";
+
+chai.use(chaiAsPromised);
+
+describe("queued withdrawals", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(
+    anchor.AnchorProvider.local(
+      "http://localhost:8899",
+      {commitment: "confirmed", preflightCommitment: "confirmed"})
+  );
+
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Staking as Program<Staking>;
+  const spl_program = anchor.Spl.token();
+
+  const pool = Keypair.generate();
+  const authority = Keypair.generate();
+
+  const rawdata = fs.readFileSync('tests/keys/mint.json');
+  const keyData = JSON.parse(rawdata.toString());
+  const mint = Keypair.fromSecretKey(new Uint8Array(keyData));
+
+  let ata: PublicKey;
+
+  before(async () => {
+    await creatMintIfRequired(spl_program, mint, provider.wallet.publicKey);
+
+    const tiers = [
+      {
+        supply: 3,
+        slots: 3,
+        minStake: new BN(5_000_000),
+        maxStake: new BN(5_000_000),
+        duration: new BN(3),
+        rewardRate: new BN(600_000_000),
+        curve: { linear: {} },
+      },
+    ];
+
+    await initializeProgram(program, pool, authority.publicKey,
+      provider.wallet.publicKey, mint.publicKey, tiers);
+
+    const [rewardVault, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+    await mintTo(spl_program, 2_000_000, mint.publicKey, rewardVault, provider.wallet.publicKey);
+
+    await provider.sendAndConfirm(new web3.Transaction().add(
+      web3.SystemProgram.transfer({
+        fromPubkey: provider.wallet.publicKey,
+        lamports: web3.LAMPORTS_PER_SOL,
+        toPubkey: authority.publicKey
+      })
+    ));
+
+    ata = await mintToATA(spl_program, authority.publicKey, new BN(0), mint.publicKey, provider.wallet.publicKey);
+  });
+
+  async function withdrawalAddress(index: number) {
+    const [withdrawal, _nonce] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("withdrawal"), pool.publicKey.toBuffer(), new BN(index).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    return withdrawal;
+  }
+
+  async function queueWithdraw(index: number, amount: number) {
+    return program.methods.queueWithdraw({ reward: {} }, new BN(amount))
+      .accounts({
+        pool: pool.publicKey,
+        withdrawal: await withdrawalAddress(index),
+        authority: authority.publicKey,
+        to: ata,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  async function executeWithdraw(index: number) {
+    return program.methods.executeWithdraw()
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+        withdrawal: await withdrawalAddress(index),
+        to: ata,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  it("Should NOT withdraw extra directly with withdraw delay", async () => {
+    await program.methods.setWithdrawDelay(new BN(3))
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    await close(program, pool.publicKey, authority);
+
+    await expect(program.methods.withdrawExtra()
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+        to: ata,
+        rewardTo: ata,
+      })
+      .signers([authority])
+      .rpc()).to.be.rejectedWith(/Withdrawals have to be queued while withdraw delay is set/);
+  });
+
+  it("Should NOT lower withdraw delay or raise it above max", async () => {
+    for (const delay of [0, 365 * 24 * 60 * 60 + 1]) {
+      await expect(program.methods.setWithdrawDelay(new BN(delay))
+        .accounts({
+          pool: pool.publicKey,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc()).to.be.rejectedWith(/Withdraw delay can only be increased, up to the max delay/);
+    }
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.withdrawDelay.toNumber()).to.be.equal(3);
+
+    await expect(program.methods.withdrawExtra()
+      .accounts({
+        pool: pool.publicKey,
+        authority: authority.publicKey,
+        to: ata,
+        rewardTo: ata,
+      })
+      .signers([authority])
+      .rpc()).to.be.rejectedWith(/Withdrawals have to be queued while withdraw delay is set/);
+  });
+
+  it("Should execute queued withdrawal after delay", async () => {
+    await queueWithdraw(0, 1_000_000);
+
+    const withdrawal = await program.account.withdrawal.fetch(await withdrawalAddress(0));
+    expect(withdrawal.amount.toNumber()).to.be.equal(1_000_000);
+    expect(withdrawal.to).to.be.deep.equal(ata);
+
+    const poolAccount = await program.account.pool.fetch(pool.publicKey);
+    expect(poolAccount.nextWithdrawal.toNumber()).to.be.equal(1);
+
+    await expect(executeWithdraw(0)).to.be.rejectedWith(/Withdrawal can't be executed before its delay has passed/);
+
+    await waitUntilblockTime(provider, withdrawal.executableAt.toNumber() + 1);
+    await executeWithdraw(0);
+
+    expect(await tokenBalance(spl_program, ata)).to.be.equal(1_000_000);
+    expect(await program.account.withdrawal.fetchNullable(await withdrawalAddress(0))).to.be.null;
+  });
+
+  it("Should cancel queued withdrawal", async () => {
+    await queueWithdraw(1, 1_000_000);
+
+    await program.methods.cancelWithdraw()
+      .accounts({
+        pool: pool.publicKey,
+        withdrawal: await withdrawalAddress(1),
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    expect(await program.account.withdrawal.fetchNullable(await withdrawalAddress(1))).to.be.null;
+  });
+
+  it("Should NOT execute withdrawal above extra", async () => {
+    await queueWithdraw(2, 2_000_000);
+
+    const withdrawal = await program.account.withdrawal.fetch(await withdrawalAddress(2));
+    await waitUntilblockTime(provider, withdrawal.executableAt.toNumber() + 1);
+
+    await expect(executeWithdraw(2)).to.be.rejectedWith(/Only extra \(total - required\) withdraw allowed/);
+  });
+});